@@ -5,9 +5,10 @@ In this contract, to endorse a message is to upvote it (a kind of like button th
 boost it's visibility in the front end). This contract also allows users to:
 - follow and unfollow specific accounts,
 - reply to regular message posts (NOT paid message posts), 
+- claim and release a unique username, 
 - declare their interests, 
 - see paid messages that fit their interests, and 
-- be paid in GEODE to endorse or upvote a paid message.
+- be paid in GEODE (or an approved PSP22 token) to endorse or upvote a paid message.
 */ 
 
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
@@ -18,8 +19,11 @@ mod geode_social {
     use ink::prelude::vec::Vec;
     use ink::prelude::vec;
     use ink::prelude::string::String;
+    use ink::prelude::collections::BTreeMap;
     use ink::storage::Mapping;
     use ink::env::hash::{Sha2x256, HashOutput};
+    use ink::env::call::{build_call, ExecutionInput, Selector};
+    use ink::env::DefaultEnvironment;
 
     // PRELIMINARY STORAGE STRUCTURES >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
 
@@ -31,7 +35,9 @@ mod geode_social {
         interests: Vec<u8>,
         max_feed: u128,
         max_paid_feed: u128,
-        last_update: u64
+        last_update: u64,
+        private: bool,
+        evict_oldest_at_limit: bool,
     }
 
     impl Default for Settings {
@@ -41,7 +47,38 @@ mod geode_social {
                 interests: <Vec<u8>>::default(),
                 max_feed: 1000,
                 max_paid_feed: 1000,
-                last_update: u64::default()
+                last_update: u64::default(),
+                private: false,
+                evict_oldest_at_limit: false,
+            }
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct ProfileDetails {
+        bio: Vec<u8>,
+        avatar_link: Vec<u8>,
+        banner_link: Vec<u8>,
+        website: Vec<u8>,
+        location: Vec<u8>,
+        pronouns: Vec<u8>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct ReleasedUsername {
+        released_by: AccountId,
+        released_at: u64,
+    }
+
+    impl Default for ReleasedUsername {
+        fn default() -> ReleasedUsername {
+            ReleasedUsername {
+                released_by: AccountId::from([0x0; 32]),
+                released_at: u64::default(),
             }
         }
     }
@@ -61,21 +98,107 @@ mod geode_social {
         }
     }
 
+    // the paged account sets kept for each account (see the set_ helpers)
+    const FOLLOWING_SET: u8 = 0;
+    const BLOCKED_SET: u8 = 1;
+    const FOLLOWERS_SET: u8 = 2;
+    const MUTED_SET: u8 = 3;
+    // the accounts that have the owner in their blocked (or muted) set
+    const BLOCKED_BY_SET: u8 = 4;
+    const MUTED_BY_SET: u8 = 5;
+    // every set a migration moves from the old account to the new one, in order
+    const GRAPH_SETS: [u8; 6] = [FOLLOWING_SET, FOLLOWERS_SET, BLOCKED_SET, MUTED_SET, BLOCKED_BY_SET, MUTED_BY_SET];
+    // set kind, owner and page number of one page of a set
+    type AccountPageKey = (u8, AccountId, u32);
+    // set kind, owner and member, gives the page the member is on
+    type AccountPositionKey = (u8, AccountId, AccountId);
+
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct AccountSetMeta {
+        len: u32,
+        next_page: u32,
+        pages: Vec<u32>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct AccountPage {
+        accounts: Vec<AccountId>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct GraphLimits {
+        max_following: u32,
+        max_blocked: u32,
+    }
+
+    // the limits every account must meet to be paid for endorsing a paid message
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct EndorsementRules {
+        daily_cap: u32,
+        wait_after_interests: u64,
+        wait_after_first_seen: u64,
+    }
+
+    // an account's paid endorsements, recent holds the last 20 message ids
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct EndorsementHistory {
+        day_start: u64,
+        today: u32,
+        total: u128,
+        last_endorsed: u64,
+        recent: Vec<Hash>,
+    }
+
+    // how a paid message funded in a PSP22 token is paid for
     #[derive(Clone, Debug, PartialEq, Eq)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
     #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
-    pub struct Blocked {
-        blocked: Vec<AccountId>,
+    pub struct TokenCampaign {
+        token: AccountId,
+        token_amount: Balance,
+        maximum_number_of_paid_endorsers: u128,
+        payment_per_endorser: Balance,
     }
 
-    impl Default for Blocked {
-        fn default() -> Blocked {
-            Blocked {
-              blocked: <Vec<AccountId>>::default(),
+    impl Default for TokenCampaign {
+        fn default() -> TokenCampaign {
+            TokenCampaign {
+                token: AccountId::from([0x0; 32]),
+                token_amount: Balance::default(),
+                maximum_number_of_paid_endorsers: u128::default(),
+                payment_per_endorser: Balance::default(),
             }
         }
     }
 
+    // how many endorsers a paid message funded in GEODE pays, and how much each
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct PaidCampaign {
+        maximum_number_of_paid_endorsers: u128,
+        payment_per_endorser: Balance,
+    }
+
+    // what an account can withdraw in one PSP22 token
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct TokenBalances {
+        claimable_refund: Balance,
+        pending_earnings: Balance,
+    }
+
     #[derive(Clone, Debug, PartialEq, Eq, Default)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
     #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
@@ -83,6 +206,41 @@ mod geode_social {
         messages: Vec<Hash>,
     }
 
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct FollowList {
+        list_id: Hash,
+        owner: AccountId,
+        name: Vec<u8>,
+        public: bool,
+        members: Vec<AccountId>,
+    }
+
+    impl Default for FollowList {
+        fn default() -> FollowList {
+            FollowList {
+                list_id: Hash::default(),
+                owner: AccountId::from([0x0; 32]),
+                name: <Vec<u8>>::default(),
+                public: false,
+                members: <Vec<AccountId>>::default(),
+            }
+        }
+    }
+
+    // who a paid message pays to endorse it: accounts with at least min_followers,
+    // first seen at least min_account_age milliseconds ago, and (if verified_only is
+    // set) that have saved their settings at least once. The default targets everyone.
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct Targeting {
+        min_followers: u128,
+        min_account_age: u64,
+        verified_only: bool,
+    }
+
     #[derive(Clone, Debug, PartialEq, Eq)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
     #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
@@ -101,6 +259,9 @@ mod geode_social {
         total_staked: Balance,
         endorsers: Vec<AccountId>,
         staked_balance: Balance,
+        active: bool,
+        targeting: Targeting,
+        token: Option<AccountId>,
     }
 
     impl Default for PaidMessageDetails {
@@ -120,11 +281,32 @@ mod geode_social {
                 total_staked: Balance::default(),
                 endorsers: <Vec<AccountId>>::default(),
                 staked_balance: Balance::default(),
+                active: false,
+                targeting: Targeting::default(),
+                token: None,
             }
         }
     }
 
 
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct BidEntry {
+        message_id: Hash,
+        bid: Balance,
+        seq: u64,
+    }
+
+    // the paid messages for one target interest, kept as a binary min-heap so that
+    // entries[0] is always the bid that would be thrown out next
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct BidHeap {
+        entries: Vec<BidEntry>,
+    }
+
     #[derive(Clone, Debug, PartialEq, Eq)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
     #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
@@ -177,6 +359,74 @@ mod geode_social {
         }
     }
 
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct GlobalFeed {
+        next_cursor: u64,
+        feed: Vec<MessageDetails>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct EndorsementWindow {
+        message_id: Hash,
+        window_start: u64,
+        current: u128,
+        previous: u128,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct Leaderboard {
+        entries: Vec<EndorsementWindow>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct TrendingMessage {
+        details: MessageDetails,
+        score: u128,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct EndorsementBucket {
+        day_start: u64,
+        endorsements: u128,
+    }
+
+    // running totals for one paid message, daily_endorsements holds the last 30 days
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct CampaignStats {
+        message_id: Hash,
+        impressions: u128,
+        endorsements: u128,
+        total_paid_out: Balance,
+        total_refunded: Balance,
+        daily_endorsements: Vec<EndorsementBucket>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct AdvertiserSummary {
+        campaigns: u128,
+        active_campaigns: u128,
+        impressions: u128,
+        endorsements: u128,
+        total_staked: Balance,
+        staked_balance: Balance,
+        total_paid_out: Balance,
+        total_refunded: Balance,
+    }
+
     #[derive(Clone, Debug, PartialEq, Eq)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
     #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
@@ -204,6 +454,7 @@ mod geode_social {
     pub struct SocialProfile {
         searched_account: AccountId,
         username: Vec<u8>,
+        details: ProfileDetails,
         followers: u128,
         following: Vec<AccountId>,
         message_list: Vec<MessageDetails>,
@@ -214,6 +465,7 @@ mod geode_social {
             SocialProfile {
                 searched_account: AccountId::from([0x0; 32]),
                 username: <Vec<u8>>::default(),
+                details: ProfileDetails::default(),
                 followers: 0,
                 following: <Vec<AccountId>>::default(),
                 message_list: <Vec<MessageDetails>>::default(),
@@ -221,6 +473,25 @@ mod geode_social {
         }
     }
 
+    #[derive(Clone, Debug, PartialEq, Eq, Default)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct Relationship {
+        following: bool,
+        followed_by: bool,
+        blocked: bool,
+        blocked_by: bool,
+        muted: bool,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
+    pub struct FollowSuggestion {
+        account: AccountId,
+        score: u128,
+    }
+
     #[derive(Clone, Debug, PartialEq, Eq)]
     #[ink::scale_derive(Encode, Decode, TypeInfo)]
     #[cfg_attr(feature = "std",derive(ink::storage::traits::StorageLayout,))]
@@ -233,6 +504,9 @@ mod geode_social {
         reward_balance: Balance,
         reward_payouts: Balance,
         claim_counter: u128,
+        paid_fee_bps: u32,
+        fee_to_reward_pool: u8,
+        treasury_balance: Balance,
     }
 
     impl Default for RewardSettings {
@@ -246,6 +520,9 @@ mod geode_social {
                 reward_balance: Balance::default(),
                 reward_payouts: Balance::default(),
                 claim_counter: u128::default(),
+                paid_fee_bps: u32::default(),
+                fee_to_reward_pool: u8::default(),
+                treasury_balance: Balance::default(),
             }
         }
     }
@@ -283,7 +560,89 @@ mod geode_social {
         paid_endorser_max: u128,
         endorser_payment: Balance,
         target_interests: Vec<u8>,
-        total_staked: Balance
+        total_staked: Balance,
+        token: Option<AccountId>,
+        targeting: Targeting,
+    }
+
+    #[ink(event)]
+    // Writes the paid message top up or bid increase to the blockchain 
+    pub struct PaidMessageUpdated {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        message_id: Hash,
+        paid_endorser_max: u128,
+        endorser_payment: Balance,
+        total_staked: Balance,
+        staked_balance: Balance,
+    }
+
+    #[ink(event)]
+    // Writes the paid message thrown out of its target interests by a higher bid to the blockchain 
+    pub struct PaidMessageOutbid {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        message_id: Hash,
+        #[ink(topic)]
+        outbid_by: Hash,
+        refunded: Balance,
+    }
+
+    #[ink(event)]
+    // Writes the advertiser refund withdrawal to the blockchain 
+    pub struct RefundWithdrawn {
+        #[ink(topic)]
+        to: AccountId,
+        amount: Balance,
+        token: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    // Writes the endorser earnings withdrawal to the blockchain 
+    pub struct EarningsWithdrawn {
+        #[ink(topic)]
+        to: AccountId,
+        amount: Balance,
+        token: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    // Writes the treasury withdrawal to the blockchain 
+    pub struct TreasuryWithdrawn {
+        #[ink(topic)]
+        to: AccountId,
+        amount: Balance,
+        token: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    // Writes the targeting of a paid message to the blockchain 
+    pub struct PaidMessageTargeted {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        message_id: Hash,
+        targeting: Targeting,
+    }
+
+    #[ink(event)]
+    // Writes the paused paid message to the blockchain 
+    pub struct PaidMessagePaused {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        message_id: Hash,
+    }
+
+    #[ink(event)]
+    // Writes the resumed paid message to the blockchain 
+    pub struct PaidMessageResumed {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        message_id: Hash,
     }
 
     #[ink(event)]
@@ -326,6 +685,71 @@ mod geode_social {
         stopped_following: AccountId,
     }
 
+    #[ink(event)]
+    // Writes the removed follower to the blockchain 
+    pub struct FollowerRemoved {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        removed: AccountId,
+    }
+
+    #[ink(event)]
+    // Writes the new follow request to the blockchain 
+    pub struct FollowRequested {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        requested_to_follow: AccountId,
+    }
+
+    #[ink(event)]
+    // Writes the rejected follow request to the blockchain 
+    pub struct FollowRequestRejected {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        rejected: AccountId,
+    }
+
+    #[ink(event)]
+    // Writes the new follow list to the blockchain 
+    pub struct ListCreated {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        list_id: Hash,
+        name: Vec<u8>,
+        public: bool,
+    }
+
+    #[ink(event)]
+    // Writes the deleted follow list to the blockchain 
+    pub struct ListDeleted {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        list_id: Hash,
+    }
+
+    #[ink(event)]
+    // Writes the new follow list member to the blockchain 
+    pub struct ListMemberAdded {
+        #[ink(topic)]
+        list_id: Hash,
+        #[ink(topic)]
+        member: AccountId,
+    }
+
+    #[ink(event)]
+    // Writes the removed follow list member to the blockchain 
+    pub struct ListMemberRemoved {
+        #[ink(topic)]
+        list_id: Hash,
+        #[ink(topic)]
+        member: AccountId,
+    }
+
     #[ink(event)]
     // Writes the new BLOCK to the blockchain 
     pub struct NewBlock {
@@ -344,6 +768,24 @@ mod geode_social {
         unblocked: AccountId,
     }
 
+    #[ink(event)]
+    // Writes the new mute to the blockchain 
+    pub struct NewMute {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        muted: AccountId,
+    }
+
+    #[ink(event)]
+    // Writes the new unmute to the blockchain 
+    pub struct NewUnMute {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        unmuted: AccountId,
+    }
+
     #[ink(event)]
     // Writes the new settings update to the blockchain 
     pub struct SettingsUpdated {
@@ -356,28 +798,118 @@ mod geode_social {
     }
 
     #[ink(event)]
-    // Writes the new reward to the blockchain 
-    pub struct AccountRewardedSocial {
+    // Writes the newly claimed username to the blockchain 
+    pub struct UsernameClaimed {
         #[ink(topic)]
-        claimant: AccountId,
-        reward: Balance,
+        from: AccountId,
+        #[ink(topic)]
+        username: Vec<u8>,
     }
 
+    #[ink(event)]
+    // Writes the newly released username to the blockchain 
+    pub struct UsernameReleased {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        username: Vec<u8>,
+    }
 
-    // ERROR DEFINITIONS >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
+    #[ink(event)]
+    // Writes the new profile details update to the blockchain 
+    pub struct ProfileUpdated {
+        #[ink(topic)]
+        from: AccountId,
+        bio: Vec<u8>,
+        avatar_link: Vec<u8>,
+        banner_link: Vec<u8>,
+        website: Vec<u8>,
+        location: Vec<u8>,
+        pronouns: Vec<u8>,
+    }
 
-    #[derive(Debug, PartialEq, Eq)]
-    #[ink::scale_derive(Encode, Decode, TypeInfo)]
-    pub enum Error {
-        // Following an account that you follow already
-        CannotFollow,
-        // Unfollowing an account that you don't follow anyway
-        NotFollowing,
-        NotInFollowerList,
-        // Blocking an account that you already blocked
+    #[ink(event)]
+    // Writes the newly pinned message to the blockchain 
+    pub struct MessagePinned {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        message_id: Hash,
+    }
+
+    #[ink(event)]
+    // Writes the newly unpinned message to the blockchain 
+    pub struct MessageUnpinned {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        message_id: Hash,
+    }
+
+    #[ink(event)]
+    // Writes the start of an account migration to the blockchain 
+    pub struct AccountMigrationInitiated {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+    }
+
+    #[ink(event)]
+    // Writes the completed account migration to the blockchain 
+    pub struct AccountMigrated {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+    }
+
+    #[ink(event)]
+    // Writes the new reward to the blockchain 
+    pub struct AccountRewardedSocial {
+        #[ink(topic)]
+        claimant: AccountId,
+        reward: Balance,
+    }
+
+
+    // ERROR DEFINITIONS >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
+
+    // the errors a PSP22 token contract can return
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Psp22Error {
+        Custom(String),
+        InsufficientBalance,
+        InsufficientAllowance,
+        ZeroRecipientAddress,
+        ZeroSenderAddress,
+        SafeTransferCheckFailed(String),
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        // Following an account that you follow already
+        CannotFollow,
+        // Unfollowing an account that you don't follow anyway
+        NotFollowing,
+        // Removing a follower that does not follow you
+        NotInFollowerList,
+        // Following more accounts than the follow limit allows
+        FollowLimitReached,
+        // Blocking more accounts than the block limit allows
+        BlockLimitReached,
+        // a restricted setting was given a value it cannot take
+        InvalidSetting,
+        // Blocking an account that you already blocked
         CannotBlock,
         // Unblocking an account that you never blocked
         NotBlocked,
+        // Muting an account that you already muted
+        CannotMute,
+        // Unmuting an account that you never muted
+        NotMuted,
         // Elevating a message that does not exist
         NonexistentMessage,
         // Elevating a paid message that does not exist
@@ -410,6 +942,51 @@ mod geode_social {
         PermissionDenied,
         // reward account payout failed
         PayoutFailed,
+        // claiming an empty username
+        InvalidUsername,
+        // releasing a username when you do not have one
+        NoUsername,
+        // claiming a recently released username before the cooldown has past
+        UsernameInCooldown,
+        // unpinning when there is no pinned message
+        NoPinnedMessage,
+        // approving or rejecting a follow request that was never made
+        NoFollowRequest,
+        // too many pending follow requests on a private account
+        FollowRequestsFull,
+        // using a follow list that does not exist
+        NonexistentList,
+        // too many lists, or too many members in a list
+        ListFull,
+        // adding an account that is already in the list
+        AlreadyInList,
+        // removing an account that is not in the list
+        NotInList,
+        // accepting an account migration that was never initiated
+        NoPendingMigration,
+        // migrating onto an account that already has social data
+        AccountNotEmpty,
+        // migrating onto an account that another account is already migrating to
+        MigrationAlreadyPending,
+        // the staked balance does not cover the remaining paid endorsements
+        InsufficientBudget,
+        // endorsing a paid message whose advertiser has paused payouts
+        CampaignPaused,
+        // endorsing a paid message without the followers, account age or
+        // verification the advertiser is targeting
+        TargetingMismatch,
+        // too many paid endorsements in one day
+        DailyEndorsementCapReached,
+        // endorsing a paid message too soon after changing your interests
+        InterestsChangedTooRecently,
+        // endorsing a paid message too soon after first using the contract
+        AccountTooNew,
+        // funding a paid message in a token that is not on the allow list
+        TokenNotAllowed,
+        // sending native coin to a token campaign, or a token amount to a native one
+        WrongPaymentCurrency,
+        // the PSP22 token contract refused or failed a transfer
+        TokenTransferFailed,
     }
 
 
@@ -418,18 +995,47 @@ mod geode_social {
     #[ink(storage)]
     pub struct ContractStorage {
         account_settings_map: Mapping<AccountId, Settings>,
-        account_following_map: Mapping<AccountId, Following>,
+        account_profile_map: Mapping<AccountId, ProfileDetails>,
+        account_set_meta_map: Mapping<(u8, AccountId), AccountSetMeta>,
+        account_set_page_map: Mapping<AccountPageKey, AccountPage>,
+        account_set_position_map: Mapping<AccountPositionKey, u32>,
+        account_follow_requests_map: Mapping<AccountId, Following>,
         account_followers_map: Mapping<AccountId, u128>,
-        account_blocked_map: Mapping<AccountId, Blocked>,
+        account_first_seen_map: Mapping<AccountId, u64>,
+        endorsement_history_map: Mapping<AccountId, EndorsementHistory>,
+        account_migration_map: Mapping<AccountId, AccountId>,
+        pending_migration_map: Mapping<AccountId, AccountId>,
+        migration_in_progress_map: Mapping<AccountId, AccountId>,
+        migrated_to_map: Mapping<AccountId, AccountId>,
+        migrated_from_map: Mapping<AccountId, AccountId>,
         account_messages_map: Mapping<AccountId, Messages>,
         account_paid_messages_map: Mapping<AccountId, Messages>,
         account_elevated_map: Mapping<AccountId, Hash>,
+        account_pinned_map: Mapping<AccountId, Hash>,
         message_map: Mapping<Hash, MessageDetails>,
         reply_map: Mapping<Hash, MessageDetails>,
         paid_message_map: Mapping<Hash, PaidMessageDetails>,
-        target_interests_map: Mapping<Vec<u8>, Messages>,
+        target_interests_map: Mapping<Vec<u8>, BidHeap>,
+        bid_counter: u64,
+        claimable_refunds_map: Mapping<AccountId, Balance>,
+        pending_earnings_map: Mapping<AccountId, Balance>,
+        token_refunds_map: Mapping<(AccountId, AccountId), Balance>,
+        token_earnings_map: Mapping<(AccountId, AccountId), Balance>,
+        account_tokens_map: Mapping<AccountId, Vec<AccountId>>,
+        campaign_stats_map: Mapping<Hash, CampaignStats>,
         message_reply_map: Mapping<Hash, Messages>,
+        list_map: Mapping<Hash, FollowList>,
+        global_feed_map: Mapping<u64, Hash>,
+        global_feed_index_map: Mapping<Hash, u64>,
+        global_feed_count: u64,
+        message_window_map: Mapping<(Hash, u8), EndorsementWindow>,
+        trending_endorsement_map: Mapping<(Hash, AccountId), ()>,
+        trending_endorsers_map: Mapping<Hash, Following>,
+        trending_map: Mapping<u8, Leaderboard>,
+        account_lists_map: Mapping<AccountId, Messages>,
         username_map: Mapping<Vec<u8>, AccountId>,
+        interest_accounts_map: Mapping<Vec<u8>, Following>,
+        released_username_map: Mapping<Vec<u8>, ReleasedUsername>,
         reward_root_set: u8,
         reward_root: AccountId,
         reward_interval: u128,
@@ -438,6 +1044,16 @@ mod geode_social {
         reward_balance: Balance,
         reward_payouts: Balance,
         claim_counter: u128,
+        paid_fee_bps: u32,
+        fee_to_reward_pool: u8,
+        treasury_balance: Balance,
+        token_treasury_map: Mapping<AccountId, Balance>,
+        allowed_tokens: Vec<AccountId>,
+        max_following: u32,
+        max_blocked: u32,
+        endorsement_daily_cap: u32,
+        wait_after_interests: u64,
+        wait_after_first_seen: u64,
     }
 
 
@@ -452,18 +1068,47 @@ mod geode_social {
         pub fn new() -> Self {
             Self {
                 account_settings_map: Mapping::default(),
-                account_following_map: Mapping::default(),
+                account_profile_map: Mapping::default(),
+                account_set_meta_map: Mapping::default(),
+                account_set_page_map: Mapping::default(),
+                account_set_position_map: Mapping::default(),
+                account_follow_requests_map: Mapping::default(),
                 account_followers_map: Mapping::default(),
-                account_blocked_map: Mapping::default(),
+                account_first_seen_map: Mapping::default(),
+                endorsement_history_map: Mapping::default(),
+                account_migration_map: Mapping::default(),
+                pending_migration_map: Mapping::default(),
+                migration_in_progress_map: Mapping::default(),
+                migrated_to_map: Mapping::default(),
+                migrated_from_map: Mapping::default(),
                 account_messages_map: Mapping::default(),
                 account_paid_messages_map: Mapping::default(),
                 account_elevated_map: Mapping::default(),
+                account_pinned_map: Mapping::default(),
                 message_map: Mapping::default(),
                 reply_map: Mapping::default(),
                 paid_message_map: Mapping::default(),
                 target_interests_map: Mapping::default(),
+                bid_counter: 0,
+                claimable_refunds_map: Mapping::default(),
+                pending_earnings_map: Mapping::default(),
+                token_refunds_map: Mapping::default(),
+                token_earnings_map: Mapping::default(),
+                account_tokens_map: Mapping::default(),
+                campaign_stats_map: Mapping::default(),
                 message_reply_map: Mapping::default(),
+                list_map: Mapping::default(),
+                global_feed_map: Mapping::default(),
+                global_feed_index_map: Mapping::default(),
+                global_feed_count: 0,
+                message_window_map: Mapping::default(),
+                trending_endorsement_map: Mapping::default(),
+                trending_endorsers_map: Mapping::default(),
+                trending_map: Mapping::default(),
+                account_lists_map: Mapping::default(),
                 username_map: Mapping::default(),
+                interest_accounts_map: Mapping::default(),
+                released_username_map: Mapping::default(),
                 reward_root_set: 0,
                 reward_root: AccountId::from([0x0; 32]),
                 reward_interval: 1000000,
@@ -472,6 +1117,16 @@ mod geode_social {
                 reward_balance: 0,
                 reward_payouts: 0,
                 claim_counter: 0,
+                paid_fee_bps: 0,
+                fee_to_reward_pool: 0,
+                treasury_balance: 0,
+                token_treasury_map: Mapping::default(),
+                allowed_tokens: Vec::new(),
+                max_following: 99,
+                max_blocked: 490,
+                endorsement_daily_cap: 10,
+                wait_after_interests: 86400000,
+                wait_after_first_seen: 86400000,
             }
         }
 
@@ -536,28 +1191,35 @@ mod geode_social {
             // UPDATE ACCOUNT MESSAGES MAP
             // get the messages vector for this account
             let caller = Self::env().caller();
+            self.mark_seen(caller);
             let mut current_messages = self.account_messages_map.get(&caller).unwrap_or_default();
             // Keep only the 3 most recent message hashes
             if current_messages.messages.len() > 2 {
                 // get the id for the oldest message
                 let oldest = current_messages.messages[0];
-                // remove the oldest from the message_map
-                self.message_map.remove(oldest);
                 // remove the oldest message from account_messages_map
                 current_messages.messages.remove(0);
-                // remove all the replies to the oldest message from the reply_map 
-                let replies = self.message_reply_map.get(oldest).unwrap_or_default();
-                for id in replies.messages.iter() {
-                    self.reply_map.remove(id);
+                // remove the oldest and its replies from storage, unless it is pinned
+                if self.account_pinned_map.get(caller) != Some(oldest) {
+                    self.remove_message(oldest);
                 }
-                // remove the oldest message from the message_reply_map
-                self.message_reply_map.remove(oldest);
             }
             // add the new message to the end of the storage
             current_messages.messages.push(new_message_id);
             // update the account_messages_map
             self.account_messages_map.insert(&caller, &current_messages);
 
+            // add the new message to the global timeline of the 500 most recent posts
+            let position = self.global_feed_count;
+            let slot = position.checked_rem_euclid(500).unwrap_or_default();
+            // if the slot is taken, the message in it falls off the timeline
+            if let Some(old_id) = self.global_feed_map.get(slot) {
+                self.global_feed_index_map.remove(old_id);
+            }
+            self.global_feed_map.insert(slot, &new_message_id);
+            self.global_feed_index_map.insert(new_message_id, &position);
+            self.global_feed_count = position.saturating_add(1);
+
             // EMIT EVENT to register the post to the chain
             Self::env().emit_event(MessageBroadcast {
                 from: Self::env().caller(),
@@ -608,180 +1270,341 @@ mod geode_social {
             payment_per_endorser: Balance,
             target_interests: Vec<u8>
         ) -> Result<(), Error> {
+            // COLLECT PAYMENT FROM THE CALLER
+            // the 'payable' tag on this message allows the user to send any amount
+            let draft = PaidMessageDetails {
+                message: new_message,
+                link: photo_or_youtube_link,
+                link2: website_or_document_link,
+                paid_endorser_max: maximum_number_of_paid_endorsers,
+                endorser_payment: payment_per_endorser,
+                target_interests,
+                total_staked: self.env().transferred_value(),
+                token: None,
+                ..Default::default()
+            };
+            self.publish_paid_message(draft)
+        }
 
-            // check that the inputs are not too long
-            // message 300 characters (600 length), links: 300 characters (600 length)
-            // target interests 50 characters (100 length)
-            if photo_or_youtube_link.len() > 600 || target_interests.len() > 100 
-            || target_interests.len() > 600 || website_or_document_link.len() > 600 {
-                // error - data too large
-                return Err(Error::DataTooLarge);
-            }
 
+        // 🟢 89 SEND PAID MESSAGE PUBLIC TARGETED
+        // the same as send_paid_message_public, but only pays endorsers that meet
+        // the targeting, starting with the very first endorsement
+        #[ink(message, payable)]
+        pub fn send_paid_message_public_targeted (&mut self, 
+            new_message: Vec<u8>,
+            photo_or_youtube_link: Vec<u8>,
+            website_or_document_link: Vec<u8>,
+            target_interests: Vec<u8>,
+            campaign: PaidCampaign,
+            targeting: Targeting,
+        ) -> Result<(), Error> {
+            let draft = PaidMessageDetails {
+                message: new_message,
+                link: photo_or_youtube_link,
+                link2: website_or_document_link,
+                paid_endorser_max: campaign.maximum_number_of_paid_endorsers,
+                endorser_payment: campaign.payment_per_endorser,
+                target_interests,
+                total_staked: self.env().transferred_value(),
+                targeting,
+                token: None,
+                ..Default::default()
+            };
+            self.publish_paid_message(draft)
+        }
+
+
+        // 🟢 86 SEND PAID MESSAGE PUBLIC IN A TOKEN
+        // the same as send_paid_message_public, but the campaign is funded with
+        // token_amount of an allow-listed PSP22 token, pulled from the caller (who must
+        // approve this contract for it first). Endorsers and refunds are paid in that token.
+        // Optional targeting applies from the first endorsement.
+        #[ink(message)]
+        pub fn send_paid_message_public_token (&mut self, 
+            new_message: Vec<u8>,
+            photo_or_youtube_link: Vec<u8>,
+            website_or_document_link: Vec<u8>,
+            target_interests: Vec<u8>,
+            campaign: TokenCampaign,
+            targeting: Option<Targeting>,
+        ) -> Result<(), Error> {
             let caller = Self::env().caller();
+            let paid_in: Balance = self.collect_payment(caller, Some(campaign.token), campaign.token_amount)?;
+            let draft = PaidMessageDetails {
+                message: new_message,
+                link: photo_or_youtube_link,
+                link2: website_or_document_link,
+                paid_endorser_max: campaign.maximum_number_of_paid_endorsers,
+                endorser_payment: campaign.payment_per_endorser,
+                target_interests,
+                total_staked: paid_in,
+                targeting: targeting.unwrap_or_default(),
+                token: Some(campaign.token),
+                ..Default::default()
+            };
+            self.publish_paid_message(draft)
+        }
 
-            let new_message_clone = new_message.clone();
-            let new_message_clone2 = new_message.clone();
-            let interests_clone = target_interests.clone();
-            let interests_clone2 = target_interests.clone();
-            let link_clone = photo_or_youtube_link.clone();
-            let link2_clone = website_or_document_link.clone();
-            
-            // CREATE THE MESSAGE ID HASH
-            // set up the data that will go into the new_message_id
-            let from = Self::env().caller();
-            let new_timestamp = self.env().block_timestamp();
-            // create the new_message_id by hashing the above data
-            let encodable = (from, new_message, new_timestamp); // Implements `scale::Encode`
-            let mut new_message_id_u8 = <Sha2x256 as HashOutput>::Type::default(); // 256-bit buffer
-            ink::env::hash_encoded::<Sha2x256, _>(&encodable, &mut new_message_id_u8);
-            let new_message_id: Hash = Hash::from(new_message_id_u8);
 
-            // COLLECT PAYMENT FROM THE CALLER
-            // the 'payable' tag on this message allows the user to send any amount
-            // so we determine here what that will give each endorser
-            let staked: Balance = self.env().transferred_value();
+        // 🟢 60 TOP UP PAID MESSAGE
+        // lets the advertiser add coin to a paid message, raise its payment per endorser
+        // and/or change its maximum number of paid endorsers. If the message was thrown
+        // out of its target interests by a higher bidder, it tries to get back in.
+        #[ink(message, payable)]
+        pub fn top_up_paid_message (&mut self, 
+            this_message_id: Hash,
+            new_maximum_number_of_paid_endorsers: u128,
+            new_payment_per_endorser: Balance,
+        ) -> Result<(), Error> {
+            self.top_up(this_message_id, new_maximum_number_of_paid_endorsers, new_payment_per_endorser, 0)
+        }
 
-            // MAKE THE PAID MESSAGE DETAILS STRUCT
-            let fromusername = self.account_settings_map.get(caller).unwrap_or_default().username;
-            // set up the paid message details
-            let new_details = PaidMessageDetails {
-                    message_id: new_message_id,
-                    from_acct: Self::env().caller(),
-                    username: fromusername,
-                    message: new_message_clone,
-                    link: photo_or_youtube_link,
-                    link2: website_or_document_link,
-                    endorser_count: 0,
-                    timestamp: self.env().block_timestamp(),
-                    paid_endorser_max: maximum_number_of_paid_endorsers,
-                    endorser_payment: payment_per_endorser,
-                    target_interests: target_interests,
-                    total_staked: staked,
-                    endorsers: vec![Self::env().caller()],
-                    staked_balance: staked,
-            };
-        
-            // if the account paid messages are full, kick out the oldest from everywhere
-            // get the messages vector for this account
-            let mut current_messages = self.account_paid_messages_map.get(&caller).unwrap_or_default();
-            // if the paid messages vector is full, remove the oldest message
-            if current_messages.messages.len() > 54 {
-                // get the id hash and interests for the oldest message
-                let oldest = current_messages.messages[0];
-                let old_interests = self.paid_message_map.get(oldest).unwrap_or_default().target_interests;
-                let old_interests_clone = old_interests.clone();
-                // remove the oldest from the paid_message_map
-                self.paid_message_map.remove(oldest);
-                // remove the oldest from the target_interests_map
-                let mut old_interests_vec = self.target_interests_map.get(old_interests).unwrap_or_default();
-                old_interests_vec.messages.retain(|value| *value != oldest);
-                self.target_interests_map.insert(old_interests_clone, &old_interests_vec);
-                // remove the oldest from the account_paid_messages_map
-                current_messages.messages.remove(0);
+
+        // 🟢 87 TOP UP PAID MESSAGE IN A TOKEN
+        // the same as top_up_paid_message for a campaign funded in a PSP22 token,
+        // adding token_amount of that token (approve this contract for it first)
+        #[ink(message)]
+        pub fn top_up_paid_message_token (&mut self, 
+            this_message_id: Hash,
+            new_maximum_number_of_paid_endorsers: u128,
+            new_payment_per_endorser: Balance,
+            token_amount: Balance,
+        ) -> Result<(), Error> {
+            self.top_up(this_message_id, new_maximum_number_of_paid_endorsers, new_payment_per_endorser, token_amount)
+        }
+
+
+        // 🟢 88 SET PAID MESSAGE TARGETING
+        // changes which endorsers a paid message pays by followers, account age and
+        // verification after it has gone out. To target from the first endorsement,
+        // send it with send_paid_message_public_targeted (or targeting in a token campaign).
+        #[ink(message)]
+        pub fn set_paid_message_targeting (&mut self, this_message_id: Hash, targeting: Targeting) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let mut details = self.paid_message_map.get(this_message_id).ok_or(Error::NonexistentPaidMessage)?;
+            if details.from_acct != caller {
+                return Err(Error::PermissionDenied);
             }
-            
-            // IF THERE ARE TOO MANY MESSAGES FOR THIS INTERESTS TARGET, THROW OUT THE LOW BIDDER
-            // get the current set of messages that match this target
-            let mut matching_messages = self.target_interests_map.get(&interests_clone).unwrap_or_default();
-            // if there are > 55 messages for this target, remove the lowest bidder
-            if matching_messages.messages.len() > 54 {
-                // determine if this message bids high enough...
-                // check the other bids and find the lowest
-                let first_hash = matching_messages.messages[0];
-                let mut low_bid: Balance = self.paid_message_map.get(first_hash).unwrap_or_default().endorser_payment;
-                let mut low_index: usize = 0;
-                for (i, ad) in matching_messages.messages.iter().enumerate() {
-                    // get the bid and index
-                    let bid: Balance = self.paid_message_map.get(ad).unwrap_or_default().endorser_payment;
-                    if bid < low_bid { 
-                        low_bid = bid;
-                        low_index = i;
-                    }
-                }
-                if payment_per_endorser > low_bid {
-                    // kick out the low bidder 
-                    matching_messages.messages.remove(low_index);
-                    // we do not remove the low bidder out of the account_paid_messages_map
-                    // or the paid_message_map becuase they will need to be able to get
-                    // their money back by endorsing their own message.
-                }
-                else {
-                    // error bid not high enough
-                    return Err(Error::BidTooLow);
-                }
+            details.targeting = targeting.clone();
+            self.paid_message_map.insert(this_message_id, &details);
+
+            // Emit an event to register the targeting to the chain
+            Self::env().emit_event(PaidMessageTargeted {
+                from: caller,
+                message_id: this_message_id,
+                targeting,
+            });
+            Ok(())
+        }
+
+
+        // 🟢 61 PAUSE PAID MESSAGE
+        // lets the advertiser stop payouts for a while (e.g. after a link breaks) without
+        // losing its place in the target interests or its staked balance
+        #[ink(message)]
+        pub fn pause_paid_message (&mut self, this_message_id: Hash) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let mut details = self.paid_message_map.get(this_message_id).ok_or(Error::NonexistentPaidMessage)?;
+            if details.from_acct != caller {
+                return Err(Error::PermissionDenied);
             }
+            details.active = false;
+            self.paid_message_map.insert(this_message_id, &details);
 
-            // add the message id and its details to the paid message_map
-            if self.paid_message_map.try_insert(&new_message_id, &new_details).is_err() {
-                return Err(Error::DataTooLarge);
+            // Emit an event to register the pause to the chain
+            Self::env().emit_event(PaidMessagePaused {
+                from: caller,
+                message_id: this_message_id,
+            });
+            Ok(())
+        }
+
+
+        // 🟢 62 RESUME PAID MESSAGE
+        #[ink(message)]
+        pub fn resume_paid_message (&mut self, this_message_id: Hash) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let mut details = self.paid_message_map.get(this_message_id).ok_or(Error::NonexistentPaidMessage)?;
+            if details.from_acct != caller {
+                return Err(Error::PermissionDenied);
             }
+            details.active = true;
+            self.paid_message_map.insert(this_message_id, &details);
 
-            // add this message to the messages vector for this account
-            current_messages.messages.push(new_message_id);
-            // update the account_messages_map
-            self.account_paid_messages_map.insert(&caller, &current_messages);
+            // Emit an event to register the resume to the chain
+            Self::env().emit_event(PaidMessageResumed {
+                from: caller,
+                message_id: this_message_id,
+            });
+            Ok(())
+        }
 
-            // add the new message to the list for these target interests
-            matching_messages.messages.push(new_message_id);
-            // update the mapping
-            self.target_interests_map.insert(&interests_clone, &matching_messages);
 
-            // EMIT AN EVENT (to register the post to the chain)
-            Self::env().emit_event(PaidMessageBroadcast {
-                from: Self::env().caller(),
-                message: new_message_clone2,
-                message_id: new_message_id,
-                link: link_clone,
-                link2: link2_clone,
-                timestamp: self.env().block_timestamp(),
-                paid_endorser_max: maximum_number_of_paid_endorsers,
-                endorser_payment: payment_per_endorser,
-                target_interests: interests_clone2,
-                total_staked: staked
+        // 🟢 85 WITHDRAW PAID MESSAGE STAKE
+        // ends a paid message's payouts and moves whatever is left of its staked balance
+        // to the advertiser's claimable refunds (in the campaign's currency). The message
+        // leaves its target interests, a top up puts it back in.
+        #[ink(message)]
+        pub fn withdraw_paid_message_stake (&mut self, this_message_id: Hash) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let mut details = self.paid_message_map.get(this_message_id).ok_or(Error::NonexistentPaidMessage)?;
+            if details.from_acct != caller {
+                return Err(Error::PermissionDenied);
+            }
+            let refund: Balance = details.staked_balance;
+            if refund == 0 {
+                return Err(Error::ZeroBalance);
+            }
+            details.staked_balance = 0;
+            self.paid_message_map.insert(this_message_id, &details);
+            self.credit_refund(caller, refund, details.token);
+            self.record_campaign_refund(this_message_id, refund);
+            // free its slot in the target interests
+            let mut bucket = self.target_interests_map.get(&details.target_interests).unwrap_or_default();
+            if Self::heap_remove(&mut bucket.entries, this_message_id).is_some() {
+                self.target_interests_map.insert(&details.target_interests, &bucket);
+            }
+
+            // Emit an event to register the update to the chain
+            Self::env().emit_event(PaidMessageUpdated {
+                from: caller,
+                message_id: this_message_id,
+                paid_endorser_max: details.paid_endorser_max,
+                endorser_payment: details.endorser_payment,
+                total_staked: details.total_staked,
+                staked_balance: details.staked_balance,
             });
+            Ok(())
+        }
 
-            // REWARD PROGRAM ACTIONS... update the claim_counter 
-            self.claim_counter = self.claim_counter.wrapping_add(1);
-            // IF conditions are met THEN payout a reward
-            let min = self.reward_amount.saturating_add(10);
-            let payout: Balance = self.reward_amount;
-            if self.reward_on == 1 && self.reward_balance > payout && self.env().balance() > min
-            && self.claim_counter.checked_rem_euclid(self.reward_interval) == Some(0) {
-                // payout
-                if self.env().transfer(caller, payout).is_err() {
-                    return Err(Error::PayoutFailed);
-                }
-                // update reward_balance
-                self.reward_balance = self.reward_balance.saturating_sub(payout);
-                // update reward_payouts
-                self.reward_payouts = self.reward_payouts.saturating_add(payout);
-                // emit an event to register the reward to the chain
-                Self::env().emit_event(AccountRewardedSocial {
-                    claimant: caller,
-                    reward: payout
-                });
+
+        // 🟢 64 WITHDRAW REFUND
+        // pays the caller the staked balances refunded from their outbid paid messages
+        #[ink(message)]
+        pub fn withdraw_refund (&mut self) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let amount: Balance = self.claimable_refunds_map.get(caller).unwrap_or_default();
+            // Check that there is a nonzero refund and the contract can pay it
+            if amount == 0 || self.env().balance() < amount {
+                return Err(Error::ZeroBalance);
+            }
+            self.claimable_refunds_map.remove(caller);
+            if self.env().transfer(caller, amount).is_err() {
+                return Err(Error::PayoutFailed);
             }
-            // END REWARD PROGRAM ACTIONS
 
+            // Emit an event to register the withdrawal to the chain
+            Self::env().emit_event(RefundWithdrawn {
+                to: caller,
+                amount,
+                token: None,
+            });
             Ok(())
+        }
 
+
+        // 🟢 66 WITHDRAW EARNINGS
+        // pays the caller everything they have earned by endorsing paid messages
+        #[ink(message)]
+        pub fn withdraw_earnings (&mut self) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let amount: Balance = self.pending_earnings_map.get(caller).unwrap_or_default();
+            // Check that there are earnings to pay and the contract can pay them
+            if amount == 0 || self.env().balance() < amount {
+                return Err(Error::ZeroBalance);
+            }
+            self.pending_earnings_map.remove(caller);
+            if self.env().transfer(caller, amount).is_err() {
+                return Err(Error::EndorserPayoutFailed);
+            }
+
+            // Emit an event to register the withdrawal to the chain
+            Self::env().emit_event(EarningsWithdrawn {
+                to: caller,
+                amount,
+                token: None,
+            });
+            Ok(())
         }
 
 
-        // 🟢 2 ELEVATE MESSAGE 
-        // upvotes a public message by endorsing it on chain (unpaid) 
+        // 🟢 79 WITHDRAW TOKEN REFUND
+        // pays the caller the refunds from their outbid or finished campaigns in a PSP22 token
         #[ink(message)]
-        pub fn elevate_message(&mut self, this_message_id: Hash) -> Result<(), Error> {
-            
-            // Does the message_id exist in the message_map? ...
-            if self.message_map.contains(&this_message_id) {
+        pub fn withdraw_token_refund (&mut self, token: AccountId) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let amount: Balance = self.token_refunds_map.get((token, caller)).unwrap_or_default();
+            if amount == 0 {
+                return Err(Error::ZeroBalance);
+            }
+            self.token_refunds_map.remove((token, caller));
+            self.drop_account_token(caller, token);
+            self.psp22_transfer(token, caller, amount)?;
+
+            // Emit an event to register the withdrawal to the chain
+            Self::env().emit_event(RefundWithdrawn {
+                to: caller,
+                amount,
+                token: Some(token),
+            });
+            Ok(())
+        }
 
-                // Get the contract caller's Account ID
-                let caller = Self::env().caller();
-                // Get the details for this message_id from the message_map
-                let current_details = self.message_map.get(&this_message_id).unwrap_or_default();
-               
+
+        // 🟢 80 WITHDRAW TOKEN EARNINGS
+        // pays the caller everything they have earned in a PSP22 token by endorsing paid messages
+        #[ink(message)]
+        pub fn withdraw_token_earnings (&mut self, token: AccountId) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let amount: Balance = self.token_earnings_map.get((token, caller)).unwrap_or_default();
+            if amount == 0 {
+                return Err(Error::ZeroBalance);
+            }
+            self.token_earnings_map.remove((token, caller));
+            self.drop_account_token(caller, token);
+            self.psp22_transfer(token, caller, amount)?;
+
+            // Emit an event to register the withdrawal to the chain
+            Self::env().emit_event(EarningsWithdrawn {
+                to: caller,
+                amount,
+                token: Some(token),
+            });
+            Ok(())
+        }
+
+
+        // 🟢 71 FETCH PAID FEED
+        // returns the same paid feed as get_paid_feed, but as a transaction that
+        // counts an impression for every paid message it returns
+        #[ink(message)]
+        pub fn fetch_paid_feed(&mut self, keyword: Vec<u8>) -> MyPaidFeed {
+            let my_paid_feed = self.get_paid_feed(keyword);
+            for details in my_paid_feed.mypaidfeed.iter() {
+                let mut stats = self.campaign_stats_map.get(details.message_id).unwrap_or_default();
+                stats.message_id = details.message_id;
+                stats.impressions = stats.impressions.saturating_add(1);
+                self.campaign_stats_map.insert(details.message_id, &stats);
+            }
+            my_paid_feed
+        }
+
+
+        // 🟢 2 ELEVATE MESSAGE 
+        // upvotes a public message by endorsing it on chain (unpaid) 
+        #[ink(message)]
+        pub fn elevate_message(&mut self, this_message_id: Hash) -> Result<(), Error> {
+            
+            // Does the message_id exist in the message_map? ...
+            if self.message_map.contains(&this_message_id) {
+
+                // Get the contract caller's Account ID
+                let caller = Self::env().caller();
+                self.mark_seen(caller);
+                // Get the details for this message_id from the message_map
+                let current_details = self.message_map.get(&this_message_id).unwrap_or_default();
+               
                 // Is it your own message?... 
                 if current_details.from_acct == caller {
                     // If TRUE, return an Error... DuplicateEndorsement
@@ -813,6 +1636,19 @@ mod geode_social {
                     // Add this message to the account_elevated_map for this caller
                     self.account_elevated_map.insert(&caller, &this_message_id);
 
+                    // count the endorsement towards the trending leaderboards,
+                    // only the first time this account endorses the message
+                    if !self.trending_endorsement_map.contains((this_message_id, caller)) {
+                        self.trending_endorsement_map.insert((this_message_id, caller), &());
+                        // remember the first 400 endorsers so their entries can be cleared
+                        let mut endorsers = self.trending_endorsers_map.get(this_message_id).unwrap_or_default();
+                        if endorsers.following.len() < 400 {
+                            endorsers.following.push(caller);
+                            self.trending_endorsers_map.insert(this_message_id, &endorsers);
+                        }
+                        self.record_trending(this_message_id);
+                    }
+
                     // Emit an event to register the endorsement to the chain...
                     Self::env().emit_event(MessageElevated {
                         from: updated_details.from_acct,
@@ -845,8 +1681,13 @@ mod geode_social {
                 // Get the details for this paid message...
                 let mut current_details = self.paid_message_map.get(&this_message_id).unwrap_or_default();
 
+                // Has the advertiser paused payouts for this message?
+                if !current_details.active {
+                    return Err(Error::CampaignPaused)
+                }
+
                 // Is the caller already in the endorsers list for this message? 
-                if current_details.endorsers.contains(&caller) {
+                if self.has_endorsed(&current_details, caller) {
                     // If TRUE, return an Error... DuplicateEndorsement
                     return Err(Error::DuplicateEndorsement)
                 } 
@@ -861,6 +1702,14 @@ mod geode_social {
                     let targetvecu8 = current_details.target_interests.clone();
                     let target_string = String::from_utf8(targetvecu8).unwrap_or_default();
                     if caller_interests_string.contains(&target_string) {
+
+                        // Does the caller meet the advertiser's targeting?
+                        if !self.meets_targeting(caller, &current_details) {
+                            return Err(Error::TargetingMismatch)
+                        }
+
+                        // Has the caller kept to the paid endorsement rules?
+                        self.check_endorsement_rules(caller)?;
                         
                         // Has this paid message hit its limit on paid endorsements?
                         let max_endorsements = current_details.paid_endorser_max;
@@ -868,21 +1717,18 @@ mod geode_social {
                         if current_endorsement_number < max_endorsements {
                             
                             // Pay the endorser the right amount from the contract
-                            let mut paythis: Balance = current_details.endorser_payment;
-                            // if the endorser is the advertiser, refund the remainder in full
-                            if caller == current_details.from_acct {
-                                paythis = current_details.staked_balance;
+                            // (the advertiser is always in the endorsers list, so it never gets here,
+                            // advertisers take back what is left with withdraw_paid_message_stake)
+                            let paythis: Balance = current_details.endorser_payment;
+                            // Check that the staked balance covers the payout
+                            if current_details.staked_balance >= paythis {
+                                // credit the endorser the amount paythis in the campaign's currency, 
+                                // they withdraw it later with withdraw_earnings
+                                self.credit_earnings(caller, paythis, current_details.token);
+                                self.record_campaign_endorsement(this_message_id, paythis);
+                                self.record_paid_endorsement(caller, this_message_id);
                             }
-                            let contractmin: Balance = paythis.saturating_add(11);
-                            // Check that there is a nonzero balance on the contract > existential deposit
-                            // plus the payout plus one
-                            if self.env().balance() > contractmin && current_details.staked_balance >= paythis {
-                                // pay the endorser the amount paythis
-                                if self.env().transfer(caller, paythis).is_err() {
-                                    return Err(Error::EndorserPayoutFailed);
-                                }
-                            }
-                            // if the balance is zero, Error (ZeroBalance)
+                            // if the staked balance has run out, Error (ZeroBalance)
                             else {
                                 return Err(Error::ZeroBalance);
                             }
@@ -916,6 +1762,9 @@ mod geode_social {
                                 total_staked: current_details.total_staked,
                                 endorsers: current_details.endorsers,
                                 staked_balance: new_balance,
+                                active: current_details.active,
+                                targeting: current_details.targeting,
+                                token: current_details.token,
                             };
 
                             // Update the paid_message_map
@@ -957,45 +1806,13 @@ mod geode_social {
 
 
         // 🟢 4 FOLLOW ACCOUNT
-        // allows a user to follow another accountId's messages
+        // allows a user to follow another accountId's messages, 
+        // following a private account sends them a follow request instead
         #[ink(message)]
         pub fn follow_account (&mut self, follow: AccountId
         ) -> Result<(), Error> {
             let caller = Self::env().caller();
-            // Is this account already being followed? or is the caller trying to follow themselves?
-            let mut current_follows = self.account_following_map.get(&caller).unwrap_or_default();
-            if current_follows.following.contains(&follow) || caller == follow {
-                return Err(Error::CannotFollow);
-            }
-            // Otherwise, update the account_following_map for this caller
-            else {
-                // if there are already > 98 accounts in the follow list, keep the most recent 99
-                if current_follows.following.len() > 98 {
-                    // kick out the oldest follow
-                    current_follows.following.remove(0);
-                }
-                // add the new follow to the the vector of accounts caller is following
-                current_follows.following.push(follow);
-                // Update (overwrite) the account_following_map entry in the storage
-                self.account_following_map.insert(&caller, &current_follows);
-                // get the number of current followers for the followed account
-                let mut current_followers = self.account_followers_map.get(&follow).unwrap_or_default(); 
-                // add the caller to the count of followers for this account
-                current_followers = current_followers.saturating_add(1);
-                // Update (overwrite) the account_followers_map entry in the storage
-                self.account_followers_map.insert(&follow, &current_followers);
-
-                // Emit an event to register the follow to the chain
-                // but only if the caller is not the follow
-                if caller != follow {
-                    Self::env().emit_event(NewFollow {
-                        from: caller,
-                        is_following: follow,
-                    });
-                }
-                
-            }
-            Ok(())
+            self.try_follow(caller, follow)
         }
 
 
@@ -1004,37 +1821,31 @@ mod geode_social {
         #[ink(message)]
         pub fn unfollow_account (&mut self, unfollow: AccountId
         ) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            self.try_unfollow(caller, unfollow)
+        }
 
-            // Is this account currently being followed? If TRUE, proceed...
+
+        // 🟢 49 REMOVE A FOLLOWER
+        // allows a user to make an account that follows them stop following them,
+        // a soft block that does not stop them from following again
+        #[ink(message)]
+        pub fn remove_follower (&mut self, follower: AccountId
+        ) -> Result<(), Error> {
             let caller = Self::env().caller();
-            let mut current_follows = self.account_following_map.get(&caller).unwrap_or_default();
-            if current_follows.following.contains(&unfollow) {
-                // remove the unfollow from the the vector of accounts they are following
-                // by keeping everyone other than that account... harsh, I know.
-                current_follows.following.retain(|value| *value != unfollow);
-                // Update (overwrite) the account_following_map entry in the storage
-                self.account_following_map.insert(&caller, &current_follows);
-
-                // reduce the followers count for the unfollow...
-                let mut current_followers = self.account_followers_map.get(&unfollow).unwrap_or_default();
-                current_followers = current_followers.saturating_sub(1);
-                // update (overwrite) the account_followers_map entry in the storage
-                self.account_followers_map.insert(&unfollow, &current_followers);
-
-                // Emit an event to register the unfollow to the chain
-                // but only if the caller is not the unfollow
-                if caller != unfollow {
-                    Self::env().emit_event(NewUnFollow {
-                        from: caller,
-                        stopped_following: unfollow,
-                    });
-                }
-                
-            }
-            // If the account is not currently being followed, ERROR: Already Not Following
-            else {
-                return Err(Error::NotFollowing);
+            // Is this account following the caller? If not, ERROR: Not In Follower List
+            if !self.set_contains(FOLLOWING_SET, follower, caller) {
+                return Err(Error::NotInFollowerList);
             }
+            // remove the caller from the follower's following list
+            // and reduce the caller's followers count
+            self.remove_follow(follower, caller);
+
+            // Emit an event to register the removal to the chain
+            Self::env().emit_event(FollowerRemoved {
+                from: caller,
+                removed: follower,
+            });
             Ok(())
         }
 
@@ -1044,34 +1855,8 @@ mod geode_social {
         #[ink(message)]
         pub fn block_account (&mut self, block: AccountId
         ) -> Result<(), Error> {
-            // Is this account already being blocked? OR is the blocked list full?
             let caller = Self::env().caller();
-            let mut current_blocked = self.account_blocked_map.get(&caller).unwrap_or_default();
-            if current_blocked.blocked.contains(&block) || caller == block {
-                return Err(Error::CannotBlock);
-            }
-            // Otherwise, update the account_blocked_map for this caller
-            else {
-                // if the blocked vector is full, kick out the oldest
-                if current_blocked.blocked.len() > 489 {
-                    current_blocked.blocked.remove(0);
-                }
-                // add the new block to the the vector of accounts caller is blocking
-                current_blocked.blocked.push(block);
-                // Update (overwrite) the account_blocked_map entry in the storage
-                self.account_blocked_map.insert(&caller, &current_blocked);
-
-                // Emit an event to register the block to the chain
-                // but only if the caller is not the block
-                if caller != block {
-                    Self::env().emit_event(NewBlock {
-                        from: caller,
-                        blocked: block,
-                    });
-                }
-                
-            }
-            Ok(())
+            self.try_block(caller, block)
         }
 
 
@@ -1080,37 +1865,124 @@ mod geode_social {
         #[ink(message)]
         pub fn unblock_account (&mut self, unblock: AccountId
         ) -> Result<(), Error> {
-            // Is this account currently being blocked? If TRUE, proceed...
             let caller = Self::env().caller();
-            let mut current_blocked = self.account_blocked_map.get(&caller).unwrap_or_default();
-            if current_blocked.blocked.contains(&unblock) {
-                // remove the unblock from the the vector of accounts they are blocking
-                // by keeping everyone other than that account... 
-                current_blocked.blocked.retain(|value| *value != unblock);
-                // Update (overwrite) the account_blocked_map entry in the storage
-                self.account_blocked_map.insert(&caller, &current_blocked);
+            self.try_unblock(caller, unblock)
+        }
 
-                // Emit an event to register the block to the chain
-                // but only if the caller is not the unblock
-                if caller != unblock {
-                    Self::env().emit_event(NewUnBlock {
-                        from: caller,
-                        unblocked: unblock,
-                    });
-                }
-                
+
+        // 🟢 53 FOLLOW ACCOUNTS (BULK)
+        // follows up to 100 accounts at once, each account is checked and reported
+        // on separately so one bad account does not stop the rest
+        #[ink(message)]
+        pub fn follow_accounts (&mut self, accounts: Vec<AccountId>
+        ) -> Result<Vec<Result<(), Error>>, Error> {
+            if accounts.len() > 100 {
+                return Err(Error::DataTooLarge);
+            }
+            let caller = Self::env().caller();
+            Ok(accounts.into_iter().map(|account| self.try_follow(caller, account)).collect())
+        }
+
+
+        // 🟢 54 UNFOLLOW ACCOUNTS (BULK)
+        #[ink(message)]
+        pub fn unfollow_accounts (&mut self, accounts: Vec<AccountId>
+        ) -> Result<Vec<Result<(), Error>>, Error> {
+            if accounts.len() > 100 {
+                return Err(Error::DataTooLarge);
+            }
+            let caller = Self::env().caller();
+            Ok(accounts.into_iter().map(|account| self.try_unfollow(caller, account)).collect())
+        }
+
+
+        // 🟢 55 BLOCK ACCOUNTS (BULK)
+        #[ink(message)]
+        pub fn block_accounts (&mut self, accounts: Vec<AccountId>
+        ) -> Result<Vec<Result<(), Error>>, Error> {
+            if accounts.len() > 100 {
+                return Err(Error::DataTooLarge);
+            }
+            let caller = Self::env().caller();
+            Ok(accounts.into_iter().map(|account| self.try_block(caller, account)).collect())
+        }
+
+
+        // 🟢 56 UNBLOCK ACCOUNTS (BULK)
+        #[ink(message)]
+        pub fn unblock_accounts (&mut self, accounts: Vec<AccountId>
+        ) -> Result<Vec<Result<(), Error>>, Error> {
+            if accounts.len() > 100 {
+                return Err(Error::DataTooLarge);
+            }
+            let caller = Self::env().caller();
+            Ok(accounts.into_iter().map(|account| self.try_unblock(caller, account)).collect())
+        }
+
+
+        // 🟢 44 MUTE AN ACCOUNT
+        // lets a user quietly hide another account in the front end without blocking them
+        #[ink(message)]
+        pub fn mute_account (&mut self, mute: AccountId) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            // mute the account an old account id has migrated to
+            let mute = self.current_account(mute);
+            if self.set_contains(MUTED_SET, caller, mute) || caller == mute {
+                return Err(Error::CannotMute);
+            }
+            self.block_or_mute(MUTED_SET, caller, mute);
+
+            // Emit an event to register the mute to the chain
+            Self::env().emit_event(NewMute {
+                from: caller,
+                muted: mute,
+            });
+            Ok(())
+        }
+
+
+        // 🟢 45 UNMUTE AN ACCOUNT
+        #[ink(message)]
+        pub fn unmute_account (&mut self, unmute: AccountId) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            // a mute of an old account id may already have moved to the account it migrated to
+            let unmute = if self.set_contains(MUTED_SET, caller, unmute) {
+                unmute
             }
-            // If the account is not currently being followed, ERROR: Already Not Following
             else {
-                return Err(Error::NotBlocked);
+                self.current_account(unmute)
+            };
+            if !self.set_contains(MUTED_SET, caller, unmute) {
+                return Err(Error::NotMuted);
             }
+            self.unblock_or_unmute(MUTED_SET, caller, unmute);
+
+            // Emit an event to register the unmute to the chain
+            Self::env().emit_event(NewUnMute {
+                from: caller,
+                unmuted: unmute,
+            });
+            Ok(())
+        }
+
+
+        // 🟢 52 SET LIMIT BEHAVIOUR
+        // by default following or blocking past the limit is an error, with evict_oldest
+        // set the oldest follow (or block) is undone instead to make room
+        #[ink(message)]
+        pub fn set_evict_oldest_at_limit (&mut self, evict_oldest: bool) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let mut settings = self.account_settings_map.get(caller).unwrap_or_default();
+            settings.evict_oldest_at_limit = evict_oldest;
+            self.account_settings_map.insert(caller, &settings);
+            self.emit_settings_updated(caller);
             Ok(())
         }
 
 
         // 🟢 8 UPDATE SETTINGS 
-        // lets a user to update their list of keyword interests and other settings 
-        // overwrites the mapping in contract storage
+        // lets a user update their username, keyword interests and feed limits all at once
+        // kept for compatibility, see set_username, set_interests and set_feed_limits
         #[ink(message)]
         pub fn update_settings (&mut self, 
             my_username: Vec<u8>,
@@ -1118,17 +1990,9 @@ mod geode_social {
             max_messages_in_my_feed: u128,
             max_messages_in_my_paid_feed: u128,
         ) -> Result<(), Error> {
-            // get the current settings for this caller and prepare the update
+            // get the current settings for this caller
             let caller = Self::env().caller();
-            let current_settings = self.account_settings_map.get(&caller).unwrap_or_default();
-            let oldname = current_settings.username;
-
-            // check that this user has not updated their settings in 24 hours
-            let time_since_last_update = self.env().block_timestamp().saturating_sub(current_settings.last_update);
-            if time_since_last_update < 86400000 {
-                // send an error that interest cannot be updated so soon
-                return Err(Error::CannotUpdateInterestsWithin24Hours)
-            }
+            let current_settings = self.account_settings_map.get(caller).unwrap_or_default();
 
             // check that the set of interest keywords and username are not too long
             // maximum length is 180 which would give us 90 characters
@@ -1137,256 +2001,780 @@ mod geode_social {
                 return Err(Error::DataTooLarge)
             }
 
-            // prepare the update
-            let settings_update: Settings = Settings {
-                username: my_username.clone(),
-                interests: my_interests.clone(),
-                max_feed: max_messages_in_my_feed,
-                max_paid_feed: max_messages_in_my_paid_feed,
-                last_update: self.env().block_timestamp()
-            };
-
-            // If, they are changing their username...
-            if my_username != oldname {
-
-                // they are not, by definition, the current owner of the new name
-                // so if the new name exists in the the username map, it is taken, send error
-                if self.username_map.contains(my_username.clone()) {
-                    // if the username belongs to someone else, send an error UsernameTaken
-                    return Err(Error::UsernameTaken)
-                }
-                else {
-                    // if the new name is not in the username map, they can have it
-                    // then update the username map
-                    self.username_map.insert(&my_username.clone(), &caller);
-                    // release the old username if the oldname it exists in the map
-                    if self.username_map.contains(oldname.clone()) {
-                        self.username_map.remove(oldname);
-                    }
-                }
-
+            // only changed fields are applied, so resending the same interests
+            // does not run into the 24 hour interests lock
+            if my_interests != current_settings.interests {
+                self.apply_interests(caller, my_interests)?;
             }
-            
-            // update their settings
-            self.account_settings_map.insert(&caller, &settings_update); 
+            if my_username != current_settings.username {
+                self.apply_username(caller, my_username)?;
+            }
+            self.apply_feed_limits(caller, max_messages_in_my_feed, max_messages_in_my_paid_feed);
 
             // Emit an event to register the update to the chain
-            Self::env().emit_event(SettingsUpdated {
-                from: caller,
-                username: my_username,
-                interests: my_interests,
-            }); 
+            self.emit_settings_updated(caller);
 
             Ok(())
         }
 
 
-        // >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
-        // >>>>>>>>>>>>>>>>>>>>>>>>>> PRIMARY GET MESSAGES <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
-        // >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
- 
-
-        // 🟢 9 GET PUBLIC FEED
-        // given an accountId, retuns the details of thhe 3 most recent public posts sent by 
-        // each account they follow, and 1 most recent endorsed post, replies on request
+        // 🟢 24 CLAIM USERNAME
+        // claims a username for the caller, releasing any name they held before
         #[ink(message)]
-        pub fn get_public_feed(&self) -> MyFeed {
-            // identify the caller
+        pub fn claim_username (&mut self, my_username: Vec<u8>) -> Result<(), Error> {
             let caller = Self::env().caller();
-            // get the list of accounts they are following as a vector of AccountIds
-            let accountvec = self.account_following_map.get(&caller).unwrap_or_default().following;
-            // set up the return data structure
-            let mut message_list: Vec<MessageDetails> = Vec::new();
-
-            // start with the caller, who will defacto follow themselves (posts only)
-            let my_idvec = self.account_messages_map.get(&caller).unwrap_or_default().messages;
-            // iterate over those messages to get the details for each
-            for messageidhash in my_idvec.iter() {
-                // get the details for that message
-                let details = self.message_map.get(&messageidhash).unwrap_or_default();
-                // add the details to the message_list vector
-                message_list.push(details);
-                // replies on request by a different message function
+            // an empty username cannot be claimed, use release_username instead
+            if my_username.is_empty() {
+                return Err(Error::InvalidUsername)
             }
-
-            // iterate over the vector of AccountIds the user is following...
-            for account in accountvec.iter() {
-                // for each AccountId they follow, get the list of message_ids sent from that account
-                let message_idvec = self.account_messages_map.get(account).unwrap_or_default().messages;
-                // iterate over those messages to get the details for each
-                for messageidhash in message_idvec.iter() {
-                    // get the details for that message
-                    let details = self.message_map.get(&messageidhash).unwrap_or_default();
-                    // add the details to the message_list vector
-                    message_list.push(details);
-                    // replies on request by a different message function
-                }
-                // then get the most recently elevated message by that account and get the details
-                let elevated_id = self.account_elevated_map.get(account).unwrap_or_default();
-                // get the details for that message
-                let details = self.message_map.get(&elevated_id).unwrap_or_default();
-                // add the details to the message_list vector
-                message_list.push(details);
+            // maximum length is 180 which would give us 90 characters
+            if my_username.len() > 180 {
+                return Err(Error::DataTooLarge)
             }
-            // At this point you should have 3 messages sent and 1 message elevated by
-            // each account you follow. It will be up to the front end to limit the display
-            // and to order them by timestamp, etc. Future events-based feeds can include more.
+            self.claim_name(caller, my_username)
+        }
 
-            // package the results
-            let my_feed = MyFeed {
-                maxfeed: self.account_settings_map.get(&caller).unwrap_or_default().max_feed,
-                blocked: self.account_blocked_map.get(&caller).unwrap_or_default().blocked,
-                myfeed: message_list
-            };
-            // return the results
-            my_feed
-           
+
+        // 🟢 25 RELEASE USERNAME
+        // gives up the caller's username so that it can be claimed again after the cooldown
+        #[ink(message)]
+        pub fn release_username (&mut self) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            if self.account_settings_map.get(caller).unwrap_or_default().username.is_empty() {
+                return Err(Error::NoUsername)
+            }
+            self.release_name(caller);
+            Ok(())
         }
 
 
-        // 🟢 10 GET PAID FEED 
-        // given an accountId, returns the details of every paid message, sent by anyone, that matches 
-        // the interests of the caller AND still has paid endorsements available AND sufficient staked balance
+        // 🟢 26 SET USERNAME
+        // sets the caller's username, or releases it if the new username is empty
         #[ink(message)]
-        pub fn get_paid_feed(&self, keyword: Vec<u8>) -> MyPaidFeed {
-            // set up the return data structure
-            let mut message_list: Vec<PaidMessageDetails> = Vec::new();
-            // make a vector of all paid message id hashes that match this keyword
-            // start by defining the caller
+        pub fn set_username (&mut self, my_username: Vec<u8>) -> Result<(), Error> {
             let caller = Self::env().caller();
-            // Get the callers list of interests...
-            let caller_interests = self.account_settings_map.get(&caller).unwrap_or_default().interests;
-            let caller_interests_string = String::from_utf8(caller_interests.clone()).unwrap_or_default();
-            // compare them to the keyword they entered
-            let target_string = String::from_utf8(keyword.clone()).unwrap_or_default();
-            // check to see if the caller's interests include the keyword
-            if caller_interests_string.contains(&target_string) {
-                // get the vector of message id hashes for that target
-                let message_idvec = self.target_interests_map.get(&keyword).unwrap_or_default().messages;
-                // Are there messages for those keywords?
-                if message_idvec.len() > 0 {
-                    // iterate over that vector of message hashes...
-                    for paidmessageid in message_idvec.iter() {
-                        // check to see if that message has endorsements and balance available
-                        // start by getting the details for that message
-                        let details = self.paid_message_map.get(&paidmessageid).unwrap_or_default();
-                        if details.endorser_count < details.paid_endorser_max && details.staked_balance > 0 {
-                            // add the details to the message_list vector
-                            message_list.push(details);
-                        }
-                        // else, if there are no paid endorsements left, or 0 balance, do nothing
-                        // repeat for the rest of the paid message ids under that target interest
-                    }
-                }                        
-            }   
-            // if the caller's interests do not match the target, do nothing
-            // At this point, you should have a complete list of messages and all their details
-            // that match the caller's interests AND have paid endorsements & balance available.
+            // maximum length is 180 which would give us 90 characters
+            if my_username.len() > 180 {
+                return Err(Error::DataTooLarge)
+            }
+            self.apply_username(caller, my_username)?;
+            self.emit_settings_updated(caller);
+            Ok(())
+        }
 
-            // package the results
-            let my_paid_feed = MyPaidFeed {
-                maxfeed: self.account_settings_map.get(&caller).unwrap_or_default().max_paid_feed,
-                myinterests: self.account_settings_map.get(&caller).unwrap_or_default().interests,
-                blocked: self.account_blocked_map.get(&caller).unwrap_or_default().blocked,
-                mypaidfeed: message_list
-            };
-            // return the results
-            my_paid_feed
 
-        }  
+        // 🟢 27 SET INTERESTS
+        // sets the caller's keyword interests, which gate paid message payouts,
+        // so this can only be done once every 24 hours
+        #[ink(message)]
+        pub fn set_interests (&mut self, my_interests: Vec<u8>) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            // maximum length is 180 which would give us 90 characters
+            if my_interests.len() > 180 {
+                return Err(Error::DataTooLarge)
+            }
+            self.apply_interests(caller, my_interests)?;
+            self.emit_settings_updated(caller);
+            Ok(())
+        }
 
 
-        // 🟢 GET THE FULL SOCIAL APP PROFILE FOR ANY GIVEN ACCOUNT 
-        // Followers, Following, all messages sent and replies and elevated/endorsed 
+        // 🟢 28 SET FEED LIMITS
+        // sets the maximum number of messages the front end shows in the caller's feeds
         #[ink(message)]
-        pub fn get_account_profile(&self, user: AccountId) -> SocialProfile {
-            // set up the return data structures
+        pub fn set_feed_limits (&mut self, 
+            max_messages_in_my_feed: u128,
+            max_messages_in_my_paid_feed: u128,
+        ) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            self.apply_feed_limits(caller, max_messages_in_my_feed, max_messages_in_my_paid_feed);
+            self.emit_settings_updated(caller);
+            Ok(())
+        }
+
+
+        // 🟢 29 SET PROFILE DETAILS
+        // lets a user describe themselves on their profile, overwrites the previous details
+        #[ink(message)]
+        pub fn set_profile_details (&mut self, 
+            bio: Vec<u8>,
+            avatar_link: Vec<u8>,
+            banner_link: Vec<u8>,
+            website: Vec<u8>,
+            location: Vec<u8>,
+            pronouns: Vec<u8>,
+        ) -> Result<(), Error> {
+            // check data limits on all inputs:
+            // bio 300 characters (600 length), links 300 characters (600 length)
+            // location and pronouns 90 characters (180 length)
+            if bio.len() > 600 || avatar_link.len() > 600 || banner_link.len() > 600 
+            || website.len() > 600 || location.len() > 180 || pronouns.len() > 180 {
+                // error - data too large
+                return Err(Error::DataTooLarge);
+            }
+
+            let caller = Self::env().caller();
+            self.mark_seen(caller);
+            let new_details = ProfileDetails {
+                bio: bio.clone(),
+                avatar_link: avatar_link.clone(),
+                banner_link: banner_link.clone(),
+                website: website.clone(),
+                location: location.clone(),
+                pronouns: pronouns.clone(),
+            };
+            if self.account_profile_map.try_insert(caller, &new_details).is_err() {
+                return Err(Error::DataTooLarge);
+            }
+
+            // Emit an event to register the update to the chain
+            Self::env().emit_event(ProfileUpdated {
+                from: caller,
+                bio,
+                avatar_link,
+                banner_link,
+                website,
+                location,
+                pronouns,
+            });
+
+            Ok(())
+        }
+
+
+        // 🟢 30 PIN MESSAGE
+        // pins one of the caller's own top level messages to their profile so that
+        // it is kept when newer posts push it out of the 3 most recent messages
+        #[ink(message)]
+        pub fn pin_message (&mut self, this_message_id: Hash) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            // the message must exist and belong to the caller
+            let details = match self.message_map.get(this_message_id) {
+                Some(details) => details,
+                None => return Err(Error::NonexistentMessage),
+            };
+            if details.from_acct != caller {
+                return Err(Error::PermissionDenied)
+            }
+            // pinning the message that is already pinned changes nothing
+            if self.account_pinned_map.get(caller) == Some(this_message_id) {
+                return Ok(())
+            }
+            // only one message can be pinned, so unpin the old one first
+            if self.account_pinned_map.contains(caller) {
+                self.unpin(caller);
+            }
+            self.account_pinned_map.insert(caller, &this_message_id);
+
+            // Emit an event to register the pin to the chain
+            Self::env().emit_event(MessagePinned {
+                from: caller,
+                message_id: this_message_id,
+            });
+
+            Ok(())
+        }
+
+
+        // 🟢 31 UNPIN MESSAGE
+        // unpins the caller's pinned message, if it has already left the 3 most 
+        // recent messages it is removed from storage along with its replies
+        #[ink(message)]
+        pub fn unpin_message (&mut self) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            if !self.account_pinned_map.contains(caller) {
+                return Err(Error::NoPinnedMessage)
+            }
+            self.unpin(caller);
+            Ok(())
+        }
+
+
+        // 🟢 32 SET ACCOUNT PRIVACY
+        // a private account must approve new followers, and only its approved 
+        // followers see its posts in their feed and on its profile
+        #[ink(message)]
+        pub fn set_account_privacy (&mut self, private: bool) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let mut settings = self.account_settings_map.get(caller).unwrap_or_default();
+            settings.private = private;
+            self.account_settings_map.insert(caller, &settings);
+            self.emit_settings_updated(caller);
+            Ok(())
+        }
+
+
+        // 🟢 33 APPROVE FOLLOW REQUEST
+        // lets a private account accept a pending follow request
+        #[ink(message)]
+        pub fn approve_follow (&mut self, requester: AccountId) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let mut requests = self.account_follow_requests_map.get(caller).unwrap_or_default();
+            if !requests.following.contains(&requester) {
+                return Err(Error::NoFollowRequest);
+            }
+            requests.following.retain(|value| *value != requester);
+            self.account_follow_requests_map.insert(caller, &requests);
+            // the requester now follows the caller, from their new account if they have
+            // migrated since making the request
+            let follower = self.current_account(requester);
+            if follower != caller && !self.set_contains(FOLLOWING_SET, follower, caller) {
+                self.add_follow(follower, caller)?;
+            }
+            Ok(())
+        }
+
+
+        // 🟢 34 REJECT FOLLOW REQUEST
+        // lets a private account turn down a pending follow request
+        #[ink(message)]
+        pub fn reject_follow (&mut self, requester: AccountId) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let mut requests = self.account_follow_requests_map.get(caller).unwrap_or_default();
+            if !requests.following.contains(&requester) {
+                return Err(Error::NoFollowRequest);
+            }
+            requests.following.retain(|value| *value != requester);
+            self.account_follow_requests_map.insert(caller, &requests);
+
+            // Emit an event to register the rejection to the chain
+            Self::env().emit_event(FollowRequestRejected {
+                from: caller,
+                rejected: requester,
+            });
+            Ok(())
+        }
+
+
+        // 🟢 36 CREATE FOLLOW LIST
+        // creates a named list of accounts (a topic) with its own feed, list 
+        // members do not count against the follow limit, private lists are owner only
+        #[ink(message)]
+        pub fn create_list (&mut self, name: Vec<u8>, public: bool) -> Result<(), Error> {
+            // maximum name length is 180 which would give us 90 characters
+            if name.len() > 180 {
+                return Err(Error::DataTooLarge);
+            }
+            let caller = Self::env().caller();
+            // keep at most 20 lists per account
+            let mut current_lists = self.account_lists_map.get(caller).unwrap_or_default();
+            if current_lists.messages.len() > 19 {
+                return Err(Error::ListFull);
+            }
+
+            // create the new list_id by hashing the owner, name and time
+            let encodable = (caller, name.clone(), self.env().block_timestamp()); // Implements `scale::Encode`
+            let mut new_list_id_u8 = <Sha2x256 as HashOutput>::Type::default(); // 256-bit buffer
+            ink::env::hash_encoded::<Sha2x256, _>(&encodable, &mut new_list_id_u8);
+            let new_list_id: Hash = Hash::from(new_list_id_u8);
+            // the same name twice in one block would give the same list_id
+            if self.list_map.contains(new_list_id) {
+                return Err(Error::ListFull);
+            }
+
+            let new_list = FollowList {
+                list_id: new_list_id,
+                owner: caller,
+                name: name.clone(),
+                public,
+                members: Vec::new(),
+            };
+            self.list_map.insert(new_list_id, &new_list);
+            current_lists.messages.push(new_list_id);
+            self.account_lists_map.insert(caller, &current_lists);
+
+            // Emit an event to register the list to the chain
+            Self::env().emit_event(ListCreated {
+                owner: caller,
+                list_id: new_list_id,
+                name,
+                public,
+            });
+            Ok(())
+        }
+
+
+        // 🟢 37 DELETE FOLLOW LIST
+        #[ink(message)]
+        pub fn delete_list (&mut self, list_id: Hash) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let list = self.list_map.get(list_id).ok_or(Error::NonexistentList)?;
+            if list.owner != caller {
+                return Err(Error::PermissionDenied);
+            }
+            self.list_map.remove(list_id);
+            let mut current_lists = self.account_lists_map.get(caller).unwrap_or_default();
+            current_lists.messages.retain(|value| *value != list_id);
+            self.account_lists_map.insert(caller, &current_lists);
+
+            // Emit an event to register the deletion to the chain
+            Self::env().emit_event(ListDeleted {
+                owner: caller,
+                list_id,
+            });
+            Ok(())
+        }
+
+
+        // 🟢 38 ADD ACCOUNT TO FOLLOW LIST
+        #[ink(message)]
+        pub fn add_to_list (&mut self, list_id: Hash, member: AccountId) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let mut list = self.list_map.get(list_id).ok_or(Error::NonexistentList)?;
+            if list.owner != caller {
+                return Err(Error::PermissionDenied);
+            }
+            if list.members.contains(&member) {
+                return Err(Error::AlreadyInList);
+            }
+            // keep at most 200 accounts per list
+            if list.members.len() > 199 {
+                return Err(Error::ListFull);
+            }
+            list.members.push(member);
+            self.list_map.insert(list_id, &list);
+
+            // Emit an event to register the new member to the chain
+            Self::env().emit_event(ListMemberAdded {
+                list_id,
+                member,
+            });
+            Ok(())
+        }
+
+
+        // 🟢 39 REMOVE ACCOUNT FROM FOLLOW LIST
+        #[ink(message)]
+        pub fn remove_from_list (&mut self, list_id: Hash, member: AccountId) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let mut list = self.list_map.get(list_id).ok_or(Error::NonexistentList)?;
+            if list.owner != caller {
+                return Err(Error::PermissionDenied);
+            }
+            if !list.members.contains(&member) {
+                return Err(Error::NotInList);
+            }
+            list.members.retain(|value| *value != member);
+            self.list_map.insert(list_id, &list);
+
+            // Emit an event to register the removed member to the chain
+            Self::env().emit_event(ListMemberRemoved {
+                list_id,
+                member,
+            });
+            Ok(())
+        }
+
+
+        // 🟢 57 INITIATE ACCOUNT MIGRATION
+        // first step of moving all of the caller's social data to a new (empty) account, 
+        // the new account must accept before anything moves. Initiating again replaces
+        // the pending migration.
+        #[ink(message)]
+        pub fn initiate_account_migration (&mut self, new_account: AccountId) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            // an account that has already moved (or is still moving in) cannot start another
+            if new_account == caller || self.migrated_to_map.contains(caller) {
+                return Err(Error::PermissionDenied);
+            }
+            if self.migration_in_progress_map.contains(caller) {
+                return Err(Error::MigrationAlreadyPending);
+            }
+            if !self.is_empty_account(new_account) {
+                return Err(Error::AccountNotEmpty);
+            }
+            // only one account at a time can be migrating to the new account
+            if let Some(other) = self.account_migration_map.get(new_account) {
+                if other != caller {
+                    return Err(Error::MigrationAlreadyPending);
+                }
+            }
+            // replace any migration this account had pending
+            if let Some(previous) = self.pending_migration_map.get(caller) {
+                self.account_migration_map.remove(previous);
+            }
+            self.pending_migration_map.insert(caller, &new_account);
+            self.account_migration_map.insert(new_account, &caller);
+
+            // Emit an event to register the migration request to the chain
+            Self::env().emit_event(AccountMigrationInitiated {
+                from: caller,
+                to: new_account,
+            });
+            Ok(())
+        }
+
+
+        // 🟢 58 CANCEL ACCOUNT MIGRATION
+        #[ink(message)]
+        pub fn cancel_account_migration (&mut self) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let new_account = self.pending_migration_map.get(caller).ok_or(Error::NoPendingMigration)?;
+            self.pending_migration_map.remove(caller);
+            self.account_migration_map.remove(new_account);
+            Ok(())
+        }
+
+
+        // 🟢 59 ACCEPT ACCOUNT MIGRATION
+        // second step, called by the new account, naming the old account it expects to
+        // take over. Moves the username, settings, profile, lists and messages over from
+        // the old account, along with the first 100 follows, followers, blocks and mutes.
+        // Larger accounts finish with continue_account_migration, AccountMigrated is
+        // emitted once everything has moved.
+        #[ink(message)]
+        pub fn accept_account_migration (&mut self, old_account: AccountId) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            if self.pending_migration_map.get(old_account) != Some(caller) {
+                return Err(Error::NoPendingMigration);
+            }
+            // the new account may have been used since the migration was initiated
+            if !self.is_empty_account(caller) {
+                return Err(Error::AccountNotEmpty);
+            }
+            self.account_migration_map.remove(caller);
+            self.pending_migration_map.remove(old_account);
+
+            self.migrate_account(old_account, caller);
+            self.migrated_to_map.insert(old_account, &caller);
+            self.migrated_from_map.insert(caller, &old_account);
+            self.migration_in_progress_map.insert(caller, &old_account);
+            self.migrate_graph_batch(old_account, caller, 100);
+            Ok(())
+        }
+
+
+        // 🟢 84 CONTINUE ACCOUNT MIGRATION
+        // called by the new account to move up to max_items more follows, followers,
+        // blocks and mutes over from the old account, until the migration is finished
+        #[ink(message)]
+        pub fn continue_account_migration (&mut self, max_items: u32) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let old_account = self.migration_in_progress_map.get(caller).ok_or(Error::NoPendingMigration)?;
+            self.migrate_graph_batch(old_account, caller, max_items);
+            Ok(())
+        }
+
+
+        // >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
+        // >>>>>>>>>>>>>>>>>>>>>>>>>> PRIMARY GET MESSAGES <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+        // >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
+ 
+
+        // 🟢 9 GET PUBLIC FEED
+        // given an accountId, retuns the details of thhe 3 most recent public posts sent by 
+        // each account they follow, and 1 most recent endorsed post, replies on request
+        #[ink(message)]
+        pub fn get_public_feed(&self) -> MyFeed {
+            // identify the caller
+            let caller = Self::env().caller();
+            // get the list of accounts they are following as a vector of AccountIds
+            let accountvec = self.set_members(FOLLOWING_SET, caller);
+            // set up the return data structure
             let mut message_list: Vec<MessageDetails> = Vec::new();
-            let user_name = self.account_settings_map.get(&user).unwrap_or_default().username;
-            let followers_count = self.account_followers_map.get(&user).unwrap_or_default();
-            let following_list = self.account_following_map.get(&user).unwrap_or_default().following;
-            
-            // get the vector of sent message_ids
-            let message_idvec = self.account_messages_map.get(&user).unwrap_or_default().messages;
-            for messageidhash in message_idvec.iter() {
+
+            // start with the caller, who will defacto follow themselves (posts only)
+            let my_idvec = self.account_messages_map.get(&caller).unwrap_or_default().messages;
+            // iterate over those messages to get the details for each
+            for messageidhash in my_idvec.iter() {
                 // get the details for that message
                 let details = self.message_map.get(&messageidhash).unwrap_or_default();
                 // add the details to the message_list vector
                 message_list.push(details);
-                // get the reply message IDs for that message
-                let reply_idvec = self.message_reply_map.get(&messageidhash).unwrap_or_default().messages;
-                // for each reply, get the details and add it to the return vector
-                for replyidhash in reply_idvec.iter() {
-                    // get the detials for that reply
-                    let replydetails = self.reply_map.get(&replyidhash).unwrap_or_default();
-                    // add the details to the message_list vector
-                    message_list.push(replydetails);
-                }
-                // loop back and do the same for each top level message from this account
+                // replies on request by a different message function
             }
-            
-            // get the vector of endorsed messasge_ids
-            let elevated_id = self.account_elevated_map.get(&user).unwrap_or_default();
-            // get the details for that message
-            let details = self.message_map.get(&elevated_id).unwrap_or_default();
-            // add the details to the message_list vector
-            message_list.push(details);
-            
+
+            // add the posts and elevated message of each account the user is following
+            message_list.append(&mut self.collect_feed(caller, &accountvec));
+            // At this point you should have 3 messages sent and 1 message elevated by
+            // each account you follow. It will be up to the front end to limit the display
+            // and to order them by timestamp, etc. Future events-based feeds can include more.
+
             // package the results
-            let social_profile = SocialProfile {
-                searched_account: user,
-                username: user_name,
-                followers: followers_count,
-                following: following_list,
-                message_list: message_list,
+            let my_feed = MyFeed {
+                maxfeed: self.account_settings_map.get(&caller).unwrap_or_default().max_feed,
+                blocked: self.set_members(BLOCKED_SET, caller),
+                myfeed: message_list
             };
             // return the results
-            social_profile
-
+            my_feed
+           
         }
 
 
-        // 🟢 12 SEND REPLY MESSAGE (REPLIES ONLY)
-        // sends a broadcast public message as a reply to a top level message on the chain
+        // 🟢 40 GET LIST FEED
+        // builds a feed the same way as get_public_feed, but only from the members
+        // of the given follow list, skipping the first cursor messages and returning
+        // at most limit messages
         #[ink(message)]
-        pub fn send_reply_public (&mut self, 
-            new_message: Vec<u8>, 
-            photo_or_youtube_link: Vec<u8>, 
-            website_or_document_link: Vec<u8>, 
-            replying_to: Hash
-        ) -> Result<(), Error> {
-
-            // check data limits on all inputs:
-            // message 300 characters (600 length), links 300 characters (600 length)
-            if new_message.len() > 600 || photo_or_youtube_link.len() > 600
-            || website_or_document_link.len() > 600 {
-                // error - data too large
-                return Err(Error::DataTooLarge);
+        pub fn get_list_feed(&self, list_id: Hash, cursor: u128, limit: u128) -> Result<MyFeed, Error> {
+            let caller = Self::env().caller();
+            let list = self.list_map.get(list_id).ok_or(Error::NonexistentList)?;
+            // private lists can only be read by their owner
+            if !list.public && list.owner != caller {
+                return Err(Error::PermissionDenied);
             }
+            let skip = usize::try_from(cursor).unwrap_or(usize::MAX);
+            let take = usize::try_from(limit).unwrap_or(usize::MAX);
+            // members who have migrated are read from their new accounts
+            let members: Vec<AccountId> = list.members.iter()
+                .map(|member| self.current_account(*member)).collect();
+            let message_list: Vec<MessageDetails> = self.collect_feed(caller, &members)
+                .into_iter().skip(skip).take(take).collect();
 
-            // Does the message exist in the top level messages? if so proceed
-            if self.message_map.contains(&replying_to) {
-                // get the vector of reply IDs for the original message
-                let mut current_replies = self.message_reply_map.get(&replying_to).unwrap_or_default();
-                
-                let new_message_clone = new_message.clone();
-                let new_message_clone2 = new_message.clone();
-                let link_clone = photo_or_youtube_link.clone();
-                let link2_clone = website_or_document_link.clone();
+            // package the results
+            let my_feed = MyFeed {
+                maxfeed: self.account_settings_map.get(caller).unwrap_or_default().max_feed,
+                blocked: self.set_members(BLOCKED_SET, caller),
+                myfeed: message_list
+            };
+            Ok(my_feed)
+        }
 
-                // set up the data that will go into the new_message_id
-                let from = Self::env().caller();
-                let new_timestamp = self.env().block_timestamp();
 
-                // create the new_message_id by hashing the above data
-                let encodable = (from, new_message, new_timestamp); // Implements `scale::Encode`
-                let mut new_message_id_u8 = <Sha2x256 as HashOutput>::Type::default(); // 256-bit buffer
-                ink::env::hash_encoded::<Sha2x256, _>(&encodable, &mut new_message_id_u8);
-                let new_message_id: Hash = Hash::from(new_message_id_u8);
+        // 🟢 42 GET GLOBAL FEED
+        // returns the most recent top level posts from all accounts, newest first,
+        // skipping authors the caller has blocked and private accounts they may not see.
+        // Start with a cursor of 0 and pass the returned next_cursor back to page
+        // through older posts, a next_cursor of 0 means there is nothing older.
+        #[ink(message)]
+        pub fn get_global_feed(&self, cursor: u64, limit: u128) -> GlobalFeed {
+            let caller = Self::env().caller();
+            let mut message_list: Vec<MessageDetails> = Vec::new();
 
-                // SET UP THE MESSAGE DETAILS FOR THE NEW REPLY
-                let caller = Self::env().caller();
-                let fromusername = self.account_settings_map.get(caller).unwrap_or_default().username;
+            // only the 500 most recent positions are still on the timeline
+            let oldest = self.global_feed_count.saturating_sub(500);
+            let mut position = if cursor == 0 || cursor > self.global_feed_count {
+                self.global_feed_count
+            } else {
+                cursor
+            };
+            while position > oldest && (message_list.len() as u128) < limit {
+                position = position.saturating_sub(1);
+                let slot = position.checked_rem_euclid(500).unwrap_or_default();
+                // skip slots whose message has since been removed
+                let message_id = match self.global_feed_map.get(slot) {
+                    Some(id) => id,
+                    None => continue,
+                };
+                if self.global_feed_index_map.get(message_id) != Some(position) {
+                    continue;
+                }
+                let details = self.message_map.get(message_id).unwrap_or_default();
+                if !self.set_contains(BLOCKED_SET, caller, details.from_acct)
+                && self.can_view(caller, details.from_acct) {
+                    message_list.push(details);
+                }
+            }
+
+            // package the results
+            let next_cursor = if position > oldest { position } else { 0 };
+            GlobalFeed {
+                next_cursor,
+                feed: message_list,
+            }
+        }
+
+
+        // 🟢 43 GET TRENDING
+        // returns up to limit messages the caller may see with the most endorsements in the recent window
+        // (0 = hour, 1 = day, 2 = week), highest score first. The score counts the 
+        // endorsements in the current window plus a share of the previous window that 
+        // shrinks as the current window goes on.
+        #[ink(message)]
+        pub fn get_trending(&self, window: u8, limit: u128) -> Vec<TrendingMessage> {
+            let caller = Self::env().caller();
+            let mut results: Vec<TrendingMessage> = Vec::new();
+            let length = match Self::trending_window_length(window) {
+                Some(length) => length,
+                None => return results,
+            };
+            let now = self.env().block_timestamp();
+            for entry in self.trending_map.get(window).unwrap_or_default().entries.iter() {
+                let score = Self::trending_score(entry, length, now);
+                // skip messages that have cooled off or have been removed
+                if score == 0 {
+                    continue;
+                }
+                if let Some(details) = self.message_map.get(entry.message_id) {
+                    // skip authors the caller has blocked and private accounts they may not see
+                    if !self.set_contains(BLOCKED_SET, caller, details.from_acct)
+                    && self.can_view(caller, details.from_acct) {
+                        results.push(TrendingMessage { details, score });
+                    }
+                }
+            }
+            // highest score first, newest first on a tie
+            results.sort_by(|a, b| b.score.cmp(&a.score)
+                .then(b.details.timestamp.cmp(&a.details.timestamp)));
+            results.truncate(usize::try_from(limit).unwrap_or(usize::MAX));
+            results
+        }
+
+
+        // 🟢 10 GET PAID FEED 
+        // given an accountId, returns the details of every paid message, sent by anyone, that matches 
+        // the interests of the caller AND still has paid endorsements available AND sufficient staked balance
+        #[ink(message)]
+        pub fn get_paid_feed(&self, keyword: Vec<u8>) -> MyPaidFeed {
+            // set up the return data structure
+            let mut message_list: Vec<PaidMessageDetails> = Vec::new();
+            // make a vector of all paid message id hashes that match this keyword
+            // start by defining the caller
+            let caller = Self::env().caller();
+            // Get the callers list of interests...
+            let caller_interests = self.account_settings_map.get(&caller).unwrap_or_default().interests;
+            let caller_interests_string = String::from_utf8(caller_interests.clone()).unwrap_or_default();
+            // compare them to the keyword they entered
+            let target_string = String::from_utf8(keyword.clone()).unwrap_or_default();
+            // check to see if the caller's interests include the keyword
+            if caller_interests_string.contains(&target_string) {
+                // get the vector of message id hashes for that target
+                let bucket = self.target_interests_map.get(&keyword).unwrap_or_default().entries;
+                // Are there messages for those keywords?
+                if !bucket.is_empty() {
+                    // iterate over that vector of message hashes...
+                    for paidmessageid in bucket.iter().map(|entry| entry.message_id) {
+                        // check to see if that message has endorsements and balance available
+                        // start by getting the details for that message
+                        let details = self.paid_message_map.get(&paidmessageid).unwrap_or_default();
+                        if details.active && details.endorser_count < details.paid_endorser_max 
+                        && details.staked_balance >= details.endorser_payment {
+                            // add the details to the message_list vector
+                            message_list.push(details);
+                        }
+                        // else, if there are no paid endorsements left, or 0 balance, do nothing
+                        // repeat for the rest of the paid message ids under that target interest
+                    }
+                }                        
+            }   
+            // if the caller's interests do not match the target, do nothing
+            // At this point, you should have a complete list of active messages and all their details
+            // that match the caller's interests AND have paid endorsements & balance available.
+
+            // package the results
+            let my_paid_feed = MyPaidFeed {
+                maxfeed: self.account_settings_map.get(&caller).unwrap_or_default().max_paid_feed,
+                myinterests: self.account_settings_map.get(&caller).unwrap_or_default().interests,
+                blocked: self.set_members(BLOCKED_SET, caller),
+                mypaidfeed: message_list
+            };
+            // return the results
+            my_paid_feed
+
+        }  
+
+
+        // 🟢 GET THE FULL SOCIAL APP PROFILE FOR ANY GIVEN ACCOUNT 
+        // Followers, Following, all messages sent and replies and elevated/endorsed 
+        #[ink(message)]
+        pub fn get_account_profile(&self, user: AccountId) -> SocialProfile {
+            // set up the return data structures
+            let mut message_list: Vec<MessageDetails> = Vec::new();
+            let user_name = self.account_settings_map.get(&user).unwrap_or_default().username;
+            let profile_details = self.account_profile_map.get(user).unwrap_or_default();
+            let followers_count = self.account_followers_map.get(&user).unwrap_or_default();
+            let following_list = self.set_members(FOLLOWING_SET, user);
+            
+            // start with the pinned message (if any), which is exempt from the rolling window
+            let pinned = self.account_pinned_map.get(user);
+            let mut message_idvec: Vec<Hash> = Vec::new();
+            // the posts of a private account are only shown to its approved followers
+            let caller = Self::env().caller();
+            if self.can_view(caller, user) {
+                if let Some(pinned_id) = pinned {
+                    message_idvec.push(pinned_id);
+                }
+                // then get the vector of sent message_ids, skipping the pinned one
+                for messageidhash in self.account_messages_map.get(user).unwrap_or_default().messages {
+                    if Some(messageidhash) != pinned {
+                        message_idvec.push(messageidhash);
+                    }
+                }
+            }
+            for messageidhash in message_idvec.iter() {
+                // get the details for that message
+                let details = self.message_map.get(&messageidhash).unwrap_or_default();
+                // add the details to the message_list vector
+                message_list.push(details);
+                // get the reply message IDs for that message
+                let reply_idvec = self.message_reply_map.get(&messageidhash).unwrap_or_default().messages;
+                // for each reply, get the details and add it to the return vector
+                for replyidhash in reply_idvec.iter() {
+                    // get the detials for that reply
+                    let replydetails = self.reply_map.get(&replyidhash).unwrap_or_default();
+                    // add the details to the message_list vector
+                    message_list.push(replydetails);
+                }
+                // loop back and do the same for each top level message from this account
+            }
+            
+            // get the vector of endorsed messasge_ids
+            let elevated_id = self.account_elevated_map.get(&user).unwrap_or_default();
+            // get the details for that message
+            let details = self.message_map.get(&elevated_id).unwrap_or_default();
+            // add the details to the message_list vector, unless it is hidden by a private account
+            if self.can_view(caller, details.from_acct) {
+                message_list.push(details);
+            }
+            
+            // package the results
+            let social_profile = SocialProfile {
+                searched_account: user,
+                username: user_name,
+                details: profile_details,
+                followers: followers_count,
+                following: following_list,
+                message_list: message_list,
+            };
+            // return the results
+            social_profile
+
+        }
+
+
+        // 🟢 12 SEND REPLY MESSAGE (REPLIES ONLY)
+        // sends a broadcast public message as a reply to a top level message on the chain
+        #[ink(message)]
+        pub fn send_reply_public (&mut self, 
+            new_message: Vec<u8>, 
+            photo_or_youtube_link: Vec<u8>, 
+            website_or_document_link: Vec<u8>, 
+            replying_to: Hash
+        ) -> Result<(), Error> {
+
+            // check data limits on all inputs:
+            // message 300 characters (600 length), links 300 characters (600 length)
+            if new_message.len() > 600 || photo_or_youtube_link.len() > 600
+            || website_or_document_link.len() > 600 {
+                // error - data too large
+                return Err(Error::DataTooLarge);
+            }
+
+            // Does the message exist in the top level messages? if so proceed
+            if self.message_map.contains(&replying_to) {
+                // get the vector of reply IDs for the original message
+                let mut current_replies = self.message_reply_map.get(&replying_to).unwrap_or_default();
+                
+                let new_message_clone = new_message.clone();
+                let new_message_clone2 = new_message.clone();
+                let link_clone = photo_or_youtube_link.clone();
+                let link2_clone = website_or_document_link.clone();
+
+                // set up the data that will go into the new_message_id
+                let from = Self::env().caller();
+                let new_timestamp = self.env().block_timestamp();
+
+                // create the new_message_id by hashing the above data
+                let encodable = (from, new_message, new_timestamp); // Implements `scale::Encode`
+                let mut new_message_id_u8 = <Sha2x256 as HashOutput>::Type::default(); // 256-bit buffer
+                ink::env::hash_encoded::<Sha2x256, _>(&encodable, &mut new_message_id_u8);
+                let new_message_id: Hash = Hash::from(new_message_id_u8);
+
+                // SET UP THE MESSAGE DETAILS FOR THE NEW REPLY
+                let caller = Self::env().caller();
+                self.mark_seen(caller);
+                let fromusername = self.account_settings_map.get(caller).unwrap_or_default().username;
                 let new_details = MessageDetails {
                     message_id: new_message_id,
                     reply_to: replying_to,
@@ -1422,226 +2810,2291 @@ mod geode_social {
                     return Err(Error::DataTooLarge);
                 }
 
-                // UPDATE THE REPLY_MAP WITH THIS REPLY'S DETAILS
-                if self.reply_map.try_insert(&new_message_id, &new_details).is_err() {
-                    return Err(Error::DataTooLarge);
+                // UPDATE THE REPLY_MAP WITH THIS REPLY'S DETAILS
+                if self.reply_map.try_insert(&new_message_id, &new_details).is_err() {
+                    return Err(Error::DataTooLarge);
+                }
+
+                // EMIT EVENT to register the post to the chain
+                Self::env().emit_event(MessageBroadcast {
+                    from: Self::env().caller(),
+                    message: new_message_clone2,
+                    message_id: new_message_id,
+                    link: link_clone,
+                    link2: link2_clone,
+                    reply_to: replying_to,
+                    timestamp: self.env().block_timestamp()
+                });
+
+            }
+            else {
+                // if the replying_to message hash does not exist, send an error
+                return Err(Error::ReplyingToMessageDoesNotExist)
+            }
+            
+            Ok(())
+        }
+
+
+        // >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
+        // >>>>>>>>>>>>>>>>>>>>>>>>>> SECONDARY GET MESSAGES <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
+        // >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
+
+        // 🟢 35 get the accounts waiting for the caller to approve their follow request
+        #[ink(message)]
+        pub fn get_pending_follow_requests(&self) -> Vec<AccountId> {
+            let caller = Self::env().caller();
+            self.account_follow_requests_map.get(caller).unwrap_or_default().following
+        }
+
+        // 🟢 41 get the follow lists made by a given AccountId (private lists for the owner only)
+        #[ink(message)]
+        pub fn get_account_lists(&self, user: AccountId) -> Vec<FollowList> {
+            let caller = Self::env().caller();
+            let mut results: Vec<FollowList> = Vec::new();
+            for id in self.account_lists_map.get(user).unwrap_or_default().messages.iter() {
+                let list = self.list_map.get(id).unwrap_or_default();
+                if list.public || caller == user {
+                    results.push(list);
+                }
+            }
+            results
+        }
+
+        // 🟢 46 get how account a relates to account b (follows, blocks and mutes in both directions)
+        #[ink(message)]
+        pub fn get_relationship(&self, a: AccountId, b: AccountId) -> Relationship {
+            Relationship {
+                following: self.set_contains(FOLLOWING_SET, a, b),
+                followed_by: self.set_contains(FOLLOWING_SET, b, a),
+                blocked: self.set_contains(BLOCKED_SET, a, b),
+                blocked_by: self.set_contains(BLOCKED_SET, b, a),
+                muted: self.set_contains(MUTED_SET, a, b),
+            }
+        }
+
+        // 🟢 47 get the accounts that a given AccountId follows and that follow them back
+        #[ink(message)]
+        pub fn get_mutual_follows(&self, user: AccountId) -> Vec<AccountId> {
+            let mut results: Vec<AccountId> = Vec::new();
+            for account in self.set_members(FOLLOWING_SET, user).iter() {
+                if self.set_contains(FOLLOWING_SET, *account, user) {
+                    results.push(*account);
+                }
+            }
+            results
+        }
+
+        // 🟢 48 get accounts the caller might want to follow, scored by how many of the
+        // caller's follows already follow them plus how many interest keywords they share
+        #[ink(message)]
+        pub fn get_follow_suggestions(&self, limit: u128) -> Vec<FollowSuggestion> {
+            let caller = Self::env().caller();
+            let mut scores: BTreeMap<AccountId, u128> = BTreeMap::new();
+
+            // accounts followed by the accounts the caller follows
+            for account in self.set_members(FOLLOWING_SET, caller) {
+                for candidate in self.set_members(FOLLOWING_SET, account) {
+                    let score = scores.entry(candidate).or_insert(0);
+                    *score = score.saturating_add(1);
+                }
+            }
+            // accounts that declared the same interest keywords as the caller
+            let interests = self.account_settings_map.get(caller).unwrap_or_default().interests;
+            for keyword in Self::interest_keywords(&interests).iter() {
+                for candidate in self.interest_accounts_map.get(keyword).unwrap_or_default().following {
+                    let score = scores.entry(candidate).or_insert(0);
+                    *score = score.saturating_add(1);
+                }
+            }
+
+            // leave out the caller, accounts they already follow and blocks either way
+            let mut results: Vec<FollowSuggestion> = Vec::new();
+            for (account, score) in scores {
+                if account == caller || self.set_contains(FOLLOWING_SET, caller, account)
+                || self.set_contains(BLOCKED_SET, caller, account)
+                || self.set_contains(BLOCKED_SET, account, caller) {
+                    continue;
+                }
+                results.push(FollowSuggestion { account, score });
+            }
+            // highest score first
+            results.sort_by_key(|suggestion| core::cmp::Reverse(suggestion.score));
+            results.truncate(usize::try_from(limit).unwrap_or(usize::MAX));
+            results
+        }
+
+        // 🟢 63 get the bid a new paid message for the given target interests must beat
+        // to get in (its payment per endorser must be higher), 0 if there is still room
+        #[ink(message)]
+        pub fn get_min_bid(&self, target_interests: Vec<u8>) -> Balance {
+            let bucket = self.target_interests_map.get(&target_interests).unwrap_or_default().entries;
+            if bucket.len() > 54 {
+                bucket.first().map(|entry| entry.bid).unwrap_or_default()
+            }
+            else {
+                0
+            }
+        }
+
+        // 🟢 65 get the refunds a given AccountId can withdraw from their outbid paid messages
+        #[ink(message)]
+        pub fn get_claimable_refund(&self, user: AccountId) -> Balance {
+            self.claimable_refunds_map.get(user).unwrap_or_default()
+        }
+
+        // 🟢 67 get the paid endorsement earnings a given AccountId can withdraw
+        #[ink(message)]
+        pub fn get_pending_earnings(&self, user: AccountId) -> Balance {
+            self.pending_earnings_map.get(user).unwrap_or_default()
+        }
+
+        // 🟢 81 get the refunds and earnings a given AccountId can withdraw in a PSP22 token
+        #[ink(message)]
+        pub fn get_token_balances(&self, user: AccountId, token: AccountId) -> TokenBalances {
+            TokenBalances {
+                claimable_refund: self.token_refunds_map.get((token, user)).unwrap_or_default(),
+                pending_earnings: self.token_earnings_map.get((token, user)).unwrap_or_default(),
+            }
+        }
+
+        // 🟢 72 get the impressions, endorsements, payouts and refunds for a paid message
+        #[ink(message)]
+        pub fn get_campaign_stats(&self, message_id: Hash) -> CampaignStats {
+            let mut stats = self.campaign_stats_map.get(message_id).unwrap_or_default();
+            stats.message_id = message_id;
+            stats
+        }
+
+        // 🟢 73 get the campaign stats for every stored paid message of a given AccountId, added up
+        #[ink(message)]
+        pub fn get_advertiser_summary(&self, user: AccountId) -> AdvertiserSummary {
+            let mut summary = AdvertiserSummary::default();
+            for message_id in self.account_paid_messages_map.get(user).unwrap_or_default().messages {
+                let details = match self.paid_message_map.get(message_id) {
+                    Some(details) => details,
+                    None => continue,
+                };
+                let stats = self.campaign_stats_map.get(message_id).unwrap_or_default();
+                summary.campaigns = summary.campaigns.saturating_add(1);
+                if details.active && details.endorser_count < details.paid_endorser_max
+                && details.staked_balance >= details.endorser_payment {
+                    summary.active_campaigns = summary.active_campaigns.saturating_add(1);
+                }
+                summary.impressions = summary.impressions.saturating_add(stats.impressions);
+                summary.endorsements = summary.endorsements.saturating_add(stats.endorsements);
+                summary.total_staked = summary.total_staked.saturating_add(details.total_staked);
+                summary.staked_balance = summary.staked_balance.saturating_add(details.staked_balance);
+                summary.total_paid_out = summary.total_paid_out.saturating_add(stats.total_paid_out);
+                summary.total_refunded = summary.total_refunded.saturating_add(stats.total_refunded);
+            }
+            summary
+        }
+
+        // 🟢 76 check whether a given AccountId would be paid for endorsing a paid message
+        // right now, returning the error elevate_paid_message would give if not
+        #[ink(message)]
+        pub fn get_endorsement_eligibility(&self, user: AccountId, message_id: Hash) -> Result<(), Error> {
+            let details = self.paid_message_map.get(message_id).ok_or(Error::NonexistentPaidMessage)?;
+            if !details.active {
+                return Err(Error::CampaignPaused)
+            }
+            if self.has_endorsed(&details, user) {
+                return Err(Error::DuplicateEndorsement)
+            }
+            let interests = self.account_settings_map.get(user).unwrap_or_default().interests;
+            let interests_string = String::from_utf8(interests).unwrap_or_default();
+            let target_string = String::from_utf8(details.target_interests.clone()).unwrap_or_default();
+            if !interests_string.contains(&target_string) {
+                return Err(Error::NoInterestMatch)
+            }
+            if !self.meets_targeting(user, &details) {
+                return Err(Error::TargetingMismatch)
+            }
+            self.check_endorsement_rules(user)?;
+            if details.endorser_count >= details.paid_endorser_max {
+                return Err(Error::NoMorePaidEndorsementsAvailable)
+            }
+            if details.staked_balance < details.endorser_payment {
+                return Err(Error::ZeroBalance)
+            }
+            Ok(())
+        }
+
+        // 🟢 13 VERIFY THAT AN ACCOUNT HAS UPDATED THEIR SETTINGS AT LEAST ONCE 
+        #[ink(message)]
+        pub fn verify_account(&self, verify: AccountId) -> u8 {
+            let mut result: u8 = 0;
+            if self.account_settings_map.contains(verify) {
+                result = 1;
+            }
+            result
+        }
+
+        // 🟢 14 GET ACCOUNT PAID MESSAGES
+        // given an accountId, returns the details of stored paid messages sent by that account
+        #[ink(message)]
+        pub fn get_account_paid_messages(&self, user: AccountId) -> Vec<PaidMessageDetails> {
+            let message_idvec = self.account_paid_messages_map.get(&user).unwrap_or_default().messages;
+            let mut message_list: Vec<PaidMessageDetails> = Vec::new();
+            for messageidhash in message_idvec.iter() {
+                // get the details for that message
+                let details = self.paid_message_map.get(&messageidhash).unwrap_or_default();
+                // add the details to the message_list vector
+                message_list.push(details);
+            }
+            // return the vector of message details
+            message_list
+        }
+
+        // 🟢 15 get the stored vector of accounts followed by a given AccountId
+        #[ink(message)]
+        pub fn get_account_following(&self, user: AccountId) -> Vec<AccountId> {
+            self.set_members(FOLLOWING_SET, user)
+        }
+
+        // 🟢 16 Get the stored details on a paid message post, given the message_id hash.  
+        #[ink(message)]
+        pub fn get_details_for_paid_message(&self, message_id: Hash
+        ) -> PaidMessageDetails {
+
+            // get the details for this message
+            let details = self.paid_message_map.get(&message_id).unwrap_or_default();
+            // return the restuls
+            details
+        }
+
+        // 🟢 17 Get the stored details on a public message post, given the message_id hash.  
+        #[ink(message)]
+        pub fn get_details_for_message(&self, message_id: Hash
+        ) -> MessageDetails {
+
+            // get the details for this message
+            let details = self.message_map.get(&message_id).unwrap_or_default();
+            // return the results
+            details
+        }
+
+        // 🟢 18 Get all the replies to a single message_id hash.
+        #[ink(message)]
+        pub fn get_replies_for_message(&self, message_id: Hash
+        ) -> Vec<MessageDetails> {
+            // set up the results vector
+            let mut results: Vec<MessageDetails> = Vec::new();
+            // get the replies for this message
+            let replies = self.message_reply_map.get(&message_id).unwrap_or_default();
+            // get the details
+            for id in replies.messages.iter() {
+                let details = self.reply_map.get(id).unwrap_or_default();
+                results.push(details);
+            }
+            // return the results
+            results
+        }
+
+
+        // >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
+        // >>>>>>>>>>>>>>>>>> REWARD PROGRAM MESSAGES >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
+        // >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
+
+
+        // 🟢 19 Rewards - Set Or Update Reward Root Account [RESTRICTED: ROOT]
+        #[ink(message)]
+        pub fn set_reward_root(&mut self, newroot: AccountId) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            // if the root is already set, send an error
+            if self.reward_root_set != 1 || self.reward_root == caller {
+                // proceed - set the roots and update the storage
+                self.reward_root = newroot;
+                self.reward_root_set = 1;
+            }
+            else {
+                // error PermissionDenied
+                return Err(Error::PermissionDenied)
+            }
+
+            Ok(())
+        }
+
+
+        // 🟢 20 Rewards - Set/Update Reward Interval and Amount [RESTRICTED: ROOT]
+        // Reward coin will be given to the account that makes the Xth claim in the system
+        #[ink(message)]
+        pub fn set_reward(&mut self, on: u8, interval: u128, amount: Balance) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            if self.reward_root == caller {
+                // proceed to set the reward program paramteters
+                self.reward_on = on;
+                self.reward_interval = interval;
+                self.reward_amount = amount;
+            }
+            else {
+                // error PermissionDenied
+                return Err(Error::PermissionDenied)
+            }
+            
+            Ok(())
+        }
+
+        // 🟢 21 ADD COIN TO REWARD ACCOUNT [RESTRICTED: ROOT]
+        #[ink(message, payable)]
+        pub fn add_reward_balance(&mut self) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            if self.reward_root == caller {
+                // add the paid in value to the reward_balance
+                let staked: Balance = self.env().transferred_value();
+                let newbalance: Balance = self.reward_balance.saturating_add(staked);
+                self.reward_balance = newbalance;
+            }
+            else {
+                // error PermissionDenied
+                return Err(Error::PermissionDenied)
+            }
+            
+            Ok(())
+        }
+
+
+        // 🟢 22 RETRIEVE COIN FROM REWARD ACCOUNT [RESTRICTED: ROOT]
+        // turns reward program off and returns funds to the root
+        #[ink(message)]
+        pub fn shut_down_reward(&mut self) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            if self.reward_root == caller {
+                // set the reward program to off
+                self.reward_on = 0;
+                // refund the coin to the reward root
+                // Check that there is a nonzero balance on the contract > existential deposit
+                if self.env().balance() > 10 && self.reward_balance > 0 {
+                    // pay the root the reward_balance minus 10
+                    let payout: Balance = self.reward_balance.saturating_sub(10);
+                    if self.env().transfer(caller, payout).is_err() {
+                        return Err(Error::PayoutFailed);
+                    }
+                }
+                // if the balance is < 10, Error (ZeroBalance)
+                else {
+                    return Err(Error::ZeroBalance);
+                }
+            }
+            else {
+                // error PermissionDenied
+                return Err(Error::PermissionDenied)
+            }
+            
+            Ok(())
+        }
+
+
+        // 🟢 50 SET FOLLOW AND BLOCK LIMITS [RESTRICTED: ROOT]
+        // the most accounts any one account can follow or block
+        #[ink(message)]
+        pub fn set_graph_limits(&mut self, max_following: u32, max_blocked: u32) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            if self.reward_root != caller {
+                // error PermissionDenied
+                return Err(Error::PermissionDenied)
+            }
+            if max_following == 0 || max_blocked == 0 {
+                return Err(Error::InvalidSetting)
+            }
+            self.max_following = max_following;
+            self.max_blocked = max_blocked;
+
+            Ok(())
+        }
+
+
+        // 🟢 51 GET FOLLOW AND BLOCK LIMITS
+        #[ink(message)]
+        pub fn get_graph_limits(&self) -> GraphLimits {
+            GraphLimits {
+                max_following: self.max_following,
+                max_blocked: self.max_blocked,
+            }
+        }
+
+
+        // 🟢 68 SET PAID MESSAGE FEE [RESTRICTED: ROOT]
+        // the platform fee in basis points (1/100th of a percent) taken from every
+        // coin staked on a paid message. With fee_to_reward_pool set to 1 the fee goes
+        // straight into the reward balance, otherwise it builds up in the treasury.
+        #[ink(message)]
+        pub fn set_paid_message_fee(&mut self, fee_bps: u32, fee_to_reward_pool: u8) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            if self.reward_root != caller {
+                // error PermissionDenied
+                return Err(Error::PermissionDenied)
+            }
+            // the fee cannot be more than the whole stake
+            if fee_bps > 10000 {
+                return Err(Error::InvalidSetting)
+            }
+            self.paid_fee_bps = fee_bps;
+            self.fee_to_reward_pool = fee_to_reward_pool;
+
+            Ok(())
+        }
+
+
+        // 🟢 69 GET TREASURY BALANCE
+        #[ink(message)]
+        pub fn get_treasury_balance(&self) -> Balance {
+            self.treasury_balance
+        }
+
+
+        // 🟢 70 WITHDRAW FROM THE TREASURY [RESTRICTED: ROOT]
+        // pays the given amount of collected platform fees to the root
+        #[ink(message)]
+        pub fn withdraw_treasury(&mut self, amount: Balance) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            if self.reward_root != caller {
+                // error PermissionDenied
+                return Err(Error::PermissionDenied)
+            }
+            // Check that the treasury holds the amount and the contract can pay it
+            if amount == 0 || amount > self.treasury_balance || self.env().balance() < amount {
+                return Err(Error::ZeroBalance);
+            }
+            self.treasury_balance = self.treasury_balance.saturating_sub(amount);
+            if self.env().transfer(caller, amount).is_err() {
+                return Err(Error::PayoutFailed);
+            }
+
+            // Emit an event to register the withdrawal to the chain
+            Self::env().emit_event(TreasuryWithdrawn {
+                to: caller,
+                amount,
+                token: None,
+            });
+            Ok(())
+        }
+
+
+        // 🟢 74 SET PAID ENDORSEMENT RULES [RESTRICTED: ROOT]
+        // the most paid endorsements an account can make per day, and how long (in
+        // milliseconds) an account must wait after changing its interests and after
+        // it was first seen before it can be paid to endorse
+        #[ink(message)]
+        pub fn set_endorsement_rules(&mut self, 
+            daily_cap: u32, 
+            wait_after_interests: u64, 
+            wait_after_first_seen: u64
+        ) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            if self.reward_root != caller {
+                // error PermissionDenied
+                return Err(Error::PermissionDenied)
+            }
+            if daily_cap == 0 {
+                return Err(Error::InvalidSetting)
+            }
+            self.endorsement_daily_cap = daily_cap;
+            self.wait_after_interests = wait_after_interests;
+            self.wait_after_first_seen = wait_after_first_seen;
+
+            Ok(())
+        }
+
+
+        // 🟢 75 GET PAID ENDORSEMENT RULES
+        #[ink(message)]
+        pub fn get_endorsement_rules(&self) -> EndorsementRules {
+            EndorsementRules {
+                daily_cap: self.endorsement_daily_cap,
+                wait_after_interests: self.wait_after_interests,
+                wait_after_first_seen: self.wait_after_first_seen,
+            }
+        }
+
+
+        // 🟢 77 ALLOW OR DISALLOW A PSP22 TOKEN FOR PAID MESSAGES [RESTRICTED: ROOT]
+        // up to 20 token contracts can be allowed at once. Disallowing a token stops new
+        // campaigns and top-ups in it, refunds and earnings can still be withdrawn.
+        #[ink(message)]
+        pub fn set_payment_token(&mut self, token: AccountId, allowed: bool) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            if self.reward_root != caller {
+                // error PermissionDenied
+                return Err(Error::PermissionDenied)
+            }
+            if allowed {
+                if !self.allowed_tokens.contains(&token) {
+                    if self.allowed_tokens.len() > 19 {
+                        return Err(Error::DataTooLarge)
+                    }
+                    self.allowed_tokens.push(token);
+                }
+            }
+            else {
+                self.allowed_tokens.retain(|value| *value != token);
+            }
+
+            Ok(())
+        }
+
+
+        // 🟢 78 GET THE PSP22 TOKENS ALLOWED FOR PAID MESSAGES
+        #[ink(message)]
+        pub fn get_payment_tokens(&self) -> Vec<AccountId> {
+            self.allowed_tokens.clone()
+        }
+
+
+        // 🟢 82 WITHDRAW FROM A TOKEN TREASURY [RESTRICTED: ROOT]
+        // pays the given amount of platform fees collected in a PSP22 token to the root
+        #[ink(message)]
+        pub fn withdraw_token_treasury(&mut self, token: AccountId, amount: Balance) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            if self.reward_root != caller {
+                // error PermissionDenied
+                return Err(Error::PermissionDenied)
+            }
+            let collected = self.token_treasury_map.get(token).unwrap_or_default();
+            if amount == 0 || amount > collected {
+                return Err(Error::ZeroBalance);
+            }
+            self.token_treasury_map.insert(token, &collected.saturating_sub(amount));
+            self.psp22_transfer(token, caller, amount)?;
+
+            // Emit an event to register the withdrawal to the chain
+            Self::env().emit_event(TreasuryWithdrawn {
+                to: caller,
+                amount,
+                token: Some(token),
+            });
+            Ok(())
+        }
+
+
+        // 🟢 83 GET A TOKEN TREASURY BALANCE
+        #[ink(message)]
+        pub fn get_token_treasury_balance(&self, token: AccountId) -> Balance {
+            self.token_treasury_map.get(token).unwrap_or_default()
+        }
+
+
+        // 🟢 23 GET CURRENT REWARD BALANCE AND SETTINGS [RESTRICTED: ROOT]
+        #[ink(message)]
+        pub fn get_reward_settings(&self) -> RewardSettings {
+            let caller = Self::env().caller();
+            let mut results = RewardSettings::default();
+            if self.reward_root == caller {
+                let settings = RewardSettings {
+                    reward_on: self.reward_on,
+                    reward_root_set: self.reward_root_set,
+                    reward_root: self.reward_root,
+                    reward_interval: self.reward_interval,
+                    reward_amount: self.reward_amount,
+                    reward_balance: self.reward_balance,
+                    reward_payouts: self.reward_payouts,
+                    claim_counter: self.claim_counter,
+                    paid_fee_bps: self.paid_fee_bps,
+                    fee_to_reward_pool: self.fee_to_reward_pool,
+                    treasury_balance: self.treasury_balance,
+                };
+                results = settings;
+            }
+
+            results
+        }
+
+        // >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
+        // >>>>>>>>>>>>>>>>>> PRIVATE HELPER FUNCTIONS >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
+        // >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
+
+
+        // sets the owner's username, an empty name releases the current one
+        fn apply_username(&mut self, owner: AccountId, name: Vec<u8>) -> Result<(), Error> {
+            self.mark_seen(owner);
+            if name.is_empty() {
+                self.release_name(owner);
+                Ok(())
+            }
+            else {
+                self.claim_name(owner, name)
+            }
+        }
+
+        // sets the owner's interests unless they were updated in the last 24 hours
+        fn apply_interests(&mut self, owner: AccountId, interests: Vec<u8>) -> Result<(), Error> {
+            let mut settings = self.account_settings_map.get(owner).unwrap_or_default();
+            // last_update records the last time the interests were changed
+            let time_since_last_update = self.env().block_timestamp().saturating_sub(settings.last_update);
+            if time_since_last_update < 86400000 {
+                // send an error that interest cannot be updated so soon
+                return Err(Error::CannotUpdateInterestsWithin24Hours)
+            }
+            // move the owner between the interest keyword indexes
+            let old_keywords = Self::interest_keywords(&settings.interests);
+            let new_keywords = Self::interest_keywords(&interests);
+            for keyword in old_keywords.iter() {
+                if !new_keywords.contains(keyword) {
+                    let mut accounts = self.interest_accounts_map.get(keyword).unwrap_or_default();
+                    accounts.following.retain(|value| *value != owner);
+                    self.interest_accounts_map.insert(keyword, &accounts);
+                }
+            }
+            for keyword in new_keywords.iter() {
+                if !old_keywords.contains(keyword) {
+                    let mut accounts = self.interest_accounts_map.get(keyword).unwrap_or_default();
+                    // keep the 100 accounts that most recently declared this keyword
+                    if accounts.following.len() > 99 {
+                        accounts.following.remove(0);
+                    }
+                    accounts.following.push(owner);
+                    self.interest_accounts_map.insert(keyword, &accounts);
+                }
+            }
+            settings.interests = interests;
+            settings.last_update = self.env().block_timestamp();
+            self.account_settings_map.insert(owner, &settings);
+            self.mark_seen(owner);
+            Ok(())
+        }
+
+        // sets the owner's feed limits, these can be changed at any time
+        fn apply_feed_limits(&mut self, owner: AccountId, max_feed: u128, max_paid_feed: u128) {
+            let mut settings = self.account_settings_map.get(owner).unwrap_or_default();
+            settings.max_feed = max_feed;
+            settings.max_paid_feed = max_paid_feed;
+            self.account_settings_map.insert(owner, &settings);
+            self.mark_seen(owner);
+        }
+
+        // emits a SettingsUpdated event with the owner's current username and interests
+        fn emit_settings_updated(&self, owner: AccountId) {
+            let settings = self.account_settings_map.get(owner).unwrap_or_default();
+            Self::env().emit_event(SettingsUpdated {
+                from: owner,
+                username: settings.username,
+                interests: settings.interests,
+            });
+        }
+
+        // adds a paid message to the bid heap for its target interests, or updates its bid
+        // if it is already there. If there are already 55 messages for that target, the 
+        // lowest bidder is thrown out, unless this message does not bid more than them.
+        fn enter_interest_bucket(&mut self, interests: &Vec<u8>, message_id: Hash, bid: Balance) -> Result<(), Error> {
+            // get the current bid heap for this target
+            let mut bucket = self.target_interests_map.get(interests).unwrap_or_default();
+            // already in the bucket, just move it to its new place in the heap
+            if let Some(mut entry) = Self::heap_remove(&mut bucket.entries, message_id) {
+                entry.bid = bid;
+                Self::heap_push(&mut bucket.entries, entry);
+                self.target_interests_map.insert(interests, &bucket);
+                return Ok(())
+            }
+            // if there are 55 messages for this target, remove the lowest bidder
+            if bucket.entries.len() > 54 {
+                let low_bid = bucket.entries.first().map(|entry| entry.bid).unwrap_or_default();
+                if bid > low_bid {
+                    // kick out the low bidder 
+                    if let Some(low_bidder) = Self::heap_pop(&mut bucket.entries) {
+                        // the low bidder stays in the account_paid_messages_map and the
+                        // paid_message_map, but its staked balance moves to the advertiser's
+                        // claimable refunds and they are told they have been outbid
+                        self.refund_outbid(low_bidder.message_id, message_id);
+                    }
+                }
+                else {
+                    // error bid not high enough
+                    return Err(Error::BidTooLow);
+                }
+            }
+            // add the message to the heap for these target interests
+            let entry = BidEntry {
+                message_id,
+                bid,
+                seq: self.bid_counter,
+            };
+            self.bid_counter = self.bid_counter.saturating_add(1);
+            Self::heap_push(&mut bucket.entries, entry);
+            // update the mapping
+            self.target_interests_map.insert(interests, &bucket);
+            Ok(())
+        }
+
+        // checks and stores a new paid message drafted by send_paid_message_public(_token),
+        // where draft.total_staked is everything paid in before the platform fee
+        fn publish_paid_message(&mut self, draft: PaidMessageDetails) -> Result<(), Error> {
+            let new_message = draft.message;
+            let photo_or_youtube_link = draft.link;
+            let website_or_document_link = draft.link2;
+            let maximum_number_of_paid_endorsers = draft.paid_endorser_max;
+            let payment_per_endorser = draft.endorser_payment;
+            let target_interests = draft.target_interests;
+            let funding_token = draft.token;
+            let targeting = draft.targeting;
+
+            // check that the inputs are not too long
+            // message 300 characters (600 length), links: 300 characters (600 length)
+            // target interests 50 characters (100 length)
+            if photo_or_youtube_link.len() > 600 || target_interests.len() > 100 
+            || target_interests.len() > 600 || website_or_document_link.len() > 600 {
+                // error - data too large
+                return Err(Error::DataTooLarge);
+            }
+
+            let caller = Self::env().caller();
+            self.mark_seen(caller);
+
+            let new_message_clone = new_message.clone();
+            let new_message_clone2 = new_message.clone();
+            let interests_clone = target_interests.clone();
+            let interests_clone2 = target_interests.clone();
+            let link_clone = photo_or_youtube_link.clone();
+            let link2_clone = website_or_document_link.clone();
+            
+            // CREATE THE MESSAGE ID HASH
+            // set up the data that will go into the new_message_id
+            let from = Self::env().caller();
+            let new_timestamp = self.env().block_timestamp();
+            // create the new_message_id by hashing the above data
+            let encodable = (from, new_message, new_timestamp); // Implements `scale::Encode`
+            let mut new_message_id_u8 = <Sha2x256 as HashOutput>::Type::default(); // 256-bit buffer
+            ink::env::hash_encoded::<Sha2x256, _>(&encodable, &mut new_message_id_u8);
+            let new_message_id: Hash = Hash::from(new_message_id_u8);
+
+            // determine what the payment will give each endorser
+            // after the platform fee comes out
+            let staked: Balance = self.take_platform_fee(draft.total_staked, funding_token);
+            // the staked balance must cover every paid endorsement on offer
+            if staked < maximum_number_of_paid_endorsers.saturating_mul(payment_per_endorser) {
+                return Err(Error::InsufficientBudget);
+            }
+
+            // MAKE THE PAID MESSAGE DETAILS STRUCT
+            let fromusername = self.account_settings_map.get(caller).unwrap_or_default().username;
+            // set up the paid message details
+            let new_details = PaidMessageDetails {
+                    message_id: new_message_id,
+                    from_acct: Self::env().caller(),
+                    username: fromusername,
+                    message: new_message_clone,
+                    link: photo_or_youtube_link,
+                    link2: website_or_document_link,
+                    endorser_count: 0,
+                    timestamp: self.env().block_timestamp(),
+                    paid_endorser_max: maximum_number_of_paid_endorsers,
+                    endorser_payment: payment_per_endorser,
+                    target_interests: target_interests,
+                    total_staked: staked,
+                    endorsers: vec![Self::env().caller()],
+                    staked_balance: staked,
+                    active: true,
+                    targeting: targeting.clone(),
+                    token: funding_token,
+            };
+        
+            // if the account paid messages are full, kick out the oldest from everywhere
+            // get the messages vector for this account
+            let mut current_messages = self.account_paid_messages_map.get(caller).unwrap_or_default();
+            // if the paid messages vector is full, remove the oldest message
+            if current_messages.messages.len() > 54 {
+                // get the id hash and interests for the oldest message
+                let oldest = current_messages.messages[0];
+                let old_interests = self.paid_message_map.get(oldest).unwrap_or_default().target_interests;
+                // refund whatever is left of the oldest and remove it from the paid_message_map
+                let old_balance = self.paid_message_map.get(oldest).unwrap_or_default().staked_balance;
+                let old_token = self.paid_message_map.get(oldest).unwrap_or_default().token;
+                self.credit_refund(caller, old_balance, old_token);
+                self.paid_message_map.remove(oldest);
+                self.campaign_stats_map.remove(oldest);
+                // remove the oldest from the target_interests_map
+                let mut old_bucket = self.target_interests_map.get(&old_interests).unwrap_or_default();
+                if Self::heap_remove(&mut old_bucket.entries, oldest).is_some() {
+                    self.target_interests_map.insert(&old_interests, &old_bucket);
+                }
+                // remove the oldest from the account_paid_messages_map
+                current_messages.messages.remove(0);
+            }
+            
+            // add the message id and its details to the paid message_map
+            if self.paid_message_map.try_insert(new_message_id, &new_details).is_err() {
+                return Err(Error::DataTooLarge);
+            }
+
+            // add this message to the messages vector for this account
+            current_messages.messages.push(new_message_id);
+            // update the account_messages_map
+            self.account_paid_messages_map.insert(caller, &current_messages);
+
+            // add the new message to the list for these target interests,
+            // throwing out the low bidder if there are too many
+            self.enter_interest_bucket(&interests_clone, new_message_id, payment_per_endorser)?;
+
+            // EMIT AN EVENT (to register the post to the chain)
+            Self::env().emit_event(PaidMessageBroadcast {
+                from: Self::env().caller(),
+                message: new_message_clone2,
+                message_id: new_message_id,
+                link: link_clone,
+                link2: link2_clone,
+                timestamp: self.env().block_timestamp(),
+                paid_endorser_max: maximum_number_of_paid_endorsers,
+                endorser_payment: payment_per_endorser,
+                target_interests: interests_clone2,
+                total_staked: staked,
+                token: funding_token,
+                targeting,
+            });
+
+            // REWARD PROGRAM ACTIONS... update the claim_counter 
+            self.claim_counter = self.claim_counter.wrapping_add(1);
+            // IF conditions are met THEN payout a reward
+            let min = self.reward_amount.saturating_add(10);
+            let payout: Balance = self.reward_amount;
+            if self.reward_on == 1 && self.reward_balance > payout && self.env().balance() > min
+            && self.claim_counter.checked_rem_euclid(self.reward_interval) == Some(0) {
+                // payout
+                if self.env().transfer(caller, payout).is_err() {
+                    return Err(Error::PayoutFailed);
+                }
+                // update reward_balance
+                self.reward_balance = self.reward_balance.saturating_sub(payout);
+                // update reward_payouts
+                self.reward_payouts = self.reward_payouts.saturating_add(payout);
+                // emit an event to register the reward to the chain
+                Self::env().emit_event(AccountRewardedSocial {
+                    claimant: caller,
+                    reward: payout
+                });
+            }
+            // END REWARD PROGRAM ACTIONS
+
+            Ok(())
+        }
+
+        // adds the payment (native coin, or token_amount of the campaign's token) to a
+        // paid message and updates its payment per endorser and maximum endorsers
+        fn top_up(&mut self, 
+            this_message_id: Hash,
+            new_maximum_number_of_paid_endorsers: u128,
+            new_payment_per_endorser: Balance,
+            token_amount: Balance,
+        ) -> Result<(), Error> {
+            let caller = Self::env().caller();
+            let mut details = self.paid_message_map.get(this_message_id).ok_or(Error::NonexistentPaidMessage)?;
+            if details.from_acct != caller {
+                return Err(Error::PermissionDenied);
+            }
+            // the payment per endorser can only go up
+            if new_payment_per_endorser < details.endorser_payment {
+                return Err(Error::BidTooLow);
+            }
+            // the staked balance must cover every paid endorsement still available
+            let paid_in: Balance = self.collect_payment(caller, details.token, token_amount)?;
+            let added: Balance = self.take_platform_fee(paid_in, details.token);
+            let new_balance: Balance = details.staked_balance.saturating_add(added);
+            let remaining = new_maximum_number_of_paid_endorsers.saturating_sub(details.endorser_count);
+            if new_maximum_number_of_paid_endorsers < details.endorser_count 
+            || new_balance < remaining.saturating_mul(new_payment_per_endorser) {
+                return Err(Error::InsufficientBudget);
+            }
+
+            details.paid_endorser_max = new_maximum_number_of_paid_endorsers;
+            details.endorser_payment = new_payment_per_endorser;
+            details.total_staked = details.total_staked.saturating_add(added);
+            details.staked_balance = new_balance;
+            if self.paid_message_map.try_insert(this_message_id, &details).is_err() {
+                return Err(Error::DataTooLarge);
+            }
+            // get back into the target interests if the message was thrown out
+            self.enter_interest_bucket(&details.target_interests, this_message_id, new_payment_per_endorser)?;
+
+            // Emit an event to register the update to the chain
+            Self::env().emit_event(PaidMessageUpdated {
+                from: caller,
+                message_id: this_message_id,
+                paid_endorser_max: details.paid_endorser_max,
+                endorser_payment: details.endorser_payment,
+                total_staked: details.total_staked,
+                staked_balance: details.staked_balance,
+            });
+            Ok(())
+        }
+
+        // ends the payouts of an outbid paid message and makes its staked balance 
+        // claimable by the advertiser
+        fn refund_outbid(&mut self, message_id: Hash, outbid_by: Hash) {
+            let mut details = match self.paid_message_map.get(message_id) {
+                Some(details) => details,
+                None => return,
+            };
+            let refund: Balance = details.staked_balance;
+            details.staked_balance = 0;
+            self.paid_message_map.insert(message_id, &details);
+            self.credit_refund(details.from_acct, refund, details.token);
+            self.record_campaign_refund(message_id, refund);
+
+            Self::env().emit_event(PaidMessageOutbid {
+                from: details.from_acct,
+                message_id,
+                outbid_by,
+                refunded: refund,
+            });
+        }
+
+        // records the first time an account did anything in the contract
+        fn mark_seen(&mut self, account: AccountId) {
+            if !self.account_first_seen_map.contains(account) {
+                self.account_first_seen_map.insert(account, &self.env().block_timestamp());
+            }
+        }
+
+        // true if the account passes the paid message's follower, account age and
+        // verified account targeting
+        fn meets_targeting(&self, account: AccountId, details: &PaidMessageDetails) -> bool {
+            let followers = self.account_followers_map.get(account).unwrap_or_default();
+            if followers < details.targeting.min_followers {
+                return false
+            }
+            if details.targeting.min_account_age > 0 {
+                let age = match self.account_first_seen_map.get(account) {
+                    Some(first_seen) => self.env().block_timestamp().saturating_sub(first_seen),
+                    None => 0,
+                };
+                if age < details.targeting.min_account_age {
+                    return false
+                }
+            }
+            !details.targeting.verified_only || self.account_settings_map.contains(account)
+        }
+
+        // the start of the day (UTC) that contains the given time
+        fn day_start(now: u64) -> u64 {
+            now.saturating_sub(now.checked_rem_euclid(86400000).unwrap_or_default())
+        }
+
+        // checks the daily cap and the waits after changing interests and after first
+        // activity that an account must meet to be paid for an endorsement
+        fn check_endorsement_rules(&self, account: AccountId) -> Result<(), Error> {
+            let now = self.env().block_timestamp();
+            let history = self.endorsement_history_map.get(account).unwrap_or_default();
+            if history.day_start == Self::day_start(now) && history.today >= self.endorsement_daily_cap {
+                return Err(Error::DailyEndorsementCapReached)
+            }
+            let last_update = self.account_settings_map.get(account).unwrap_or_default().last_update;
+            if now.saturating_sub(last_update) < self.wait_after_interests {
+                return Err(Error::InterestsChangedTooRecently)
+            }
+            let first_seen = self.account_first_seen_map.get(account).unwrap_or(now);
+            if now.saturating_sub(first_seen) < self.wait_after_first_seen {
+                return Err(Error::AccountTooNew)
+            }
+            Ok(())
+        }
+
+        // adds a paid endorsement to the account's endorsement history
+        fn record_paid_endorsement(&mut self, account: AccountId, message_id: Hash) {
+            let now = self.env().block_timestamp();
+            let mut history = self.endorsement_history_map.get(account).unwrap_or_default();
+            let today = Self::day_start(now);
+            if history.day_start != today {
+                history.day_start = today;
+                history.today = 0;
+            }
+            history.today = history.today.saturating_add(1);
+            history.total = history.total.saturating_add(1);
+            history.last_endorsed = now;
+            // keep only the 20 most recent
+            if history.recent.len() > 19 {
+                history.recent.remove(0);
+            }
+            history.recent.push(message_id);
+            self.endorsement_history_map.insert(account, &history);
+        }
+
+        // counts a paid endorsement in the campaign stats, by day
+        fn record_campaign_endorsement(&mut self, message_id: Hash, paid: Balance) {
+            let now = self.env().block_timestamp();
+            let day_start = Self::day_start(now);
+            let mut stats = self.campaign_stats_map.get(message_id).unwrap_or_default();
+            stats.message_id = message_id;
+            stats.endorsements = stats.endorsements.saturating_add(1);
+            stats.total_paid_out = stats.total_paid_out.saturating_add(paid);
+            match stats.daily_endorsements.last_mut() {
+                Some(bucket) if bucket.day_start == day_start => {
+                    bucket.endorsements = bucket.endorsements.saturating_add(1);
+                }
+                _ => {
+                    // keep only the last 30 days
+                    if stats.daily_endorsements.len() > 29 {
+                        stats.daily_endorsements.remove(0);
+                    }
+                    stats.daily_endorsements.push(EndorsementBucket { day_start, endorsements: 1 });
+                }
+            }
+            self.campaign_stats_map.insert(message_id, &stats);
+        }
+
+        // counts coin handed back to the advertiser in the campaign stats
+        fn record_campaign_refund(&mut self, message_id: Hash, refund: Balance) {
+            if refund == 0 {
+                return
+            }
+            let mut stats = self.campaign_stats_map.get(message_id).unwrap_or_default();
+            stats.message_id = message_id;
+            stats.total_refunded = stats.total_refunded.saturating_add(refund);
+            self.campaign_stats_map.insert(message_id, &stats);
+        }
+
+        // takes the platform fee out of coin staked on a paid message and returns
+        // what is left for the endorsers
+        fn take_platform_fee(&mut self, amount: Balance, token: Option<AccountId>) -> Balance {
+            let fee: Balance = amount.saturating_mul(Balance::from(self.paid_fee_bps))
+                .checked_div(10000).unwrap_or_default();
+            // fees in a token always go to that token's treasury
+            if let Some(token) = token {
+                let collected = self.token_treasury_map.get(token).unwrap_or_default();
+                self.token_treasury_map.insert(token, &collected.saturating_add(fee));
+            }
+            else if self.fee_to_reward_pool == 1 {
+                self.reward_balance = self.reward_balance.saturating_add(fee);
+            }
+            else {
+                self.treasury_balance = self.treasury_balance.saturating_add(fee);
+            }
+            amount.saturating_sub(fee)
+        }
+
+        // adds to the advertiser's claimable refunds in the campaign's currency
+        fn credit_refund(&mut self, advertiser: AccountId, amount: Balance, token: Option<AccountId>) {
+            if amount == 0 {
+                return
+            }
+            if let Some(token) = token {
+                let claimable = self.token_refunds_map.get((token, advertiser)).unwrap_or_default();
+                self.token_refunds_map.insert((token, advertiser), &claimable.saturating_add(amount));
+                self.add_account_token(advertiser, token);
+            }
+            else {
+                let claimable = self.claimable_refunds_map.get(advertiser).unwrap_or_default();
+                self.claimable_refunds_map.insert(advertiser, &claimable.saturating_add(amount));
+            }
+        }
+
+        // adds to the endorser's pending earnings in the campaign's currency
+        fn credit_earnings(&mut self, endorser: AccountId, amount: Balance, token: Option<AccountId>) {
+            if let Some(token) = token {
+                let earned = self.token_earnings_map.get((token, endorser)).unwrap_or_default();
+                self.token_earnings_map.insert((token, endorser), &earned.saturating_add(amount));
+                self.add_account_token(endorser, token);
+            }
+            else {
+                let earned = self.pending_earnings_map.get(endorser).unwrap_or_default();
+                self.pending_earnings_map.insert(endorser, &earned.saturating_add(amount));
+            }
+        }
+
+        // remembers that the account has refunds or earnings in the token
+        fn add_account_token(&mut self, account: AccountId, token: AccountId) {
+            let mut tokens = self.account_tokens_map.get(account).unwrap_or_default();
+            if !tokens.contains(&token) {
+                tokens.push(token);
+                self.account_tokens_map.insert(account, &tokens);
+            }
+        }
+
+        // forgets the token for the account once it has nothing left to withdraw in it
+        fn drop_account_token(&mut self, account: AccountId, token: AccountId) {
+            if self.token_refunds_map.contains((token, account)) 
+            || self.token_earnings_map.contains((token, account)) {
+                return
+            }
+            let mut tokens = self.account_tokens_map.get(account).unwrap_or_default();
+            tokens.retain(|value| *value != token);
+            if tokens.is_empty() {
+                self.account_tokens_map.remove(account);
+            }
+            else {
+                self.account_tokens_map.insert(account, &tokens);
+            }
+        }
+
+        // takes the payment for a paid message: the native coin sent with the call, or
+        // token_amount of an allow-listed PSP22 token pulled from the payer
+        fn collect_payment(&mut self, payer: AccountId, token: Option<AccountId>, token_amount: Balance
+        ) -> Result<Balance, Error> {
+            match token {
+                Some(token) => {
+                    if !self.allowed_tokens.contains(&token) {
+                        return Err(Error::TokenNotAllowed)
+                    }
+                    if self.env().transferred_value() > 0 {
+                        return Err(Error::WrongPaymentCurrency)
+                    }
+                    if token_amount > 0 {
+                        let contract = self.env().account_id();
+                        self.psp22_transfer_from(token, payer, contract, token_amount)?;
+                    }
+                    Ok(token_amount)
+                }
+                None => {
+                    if token_amount > 0 {
+                        return Err(Error::WrongPaymentCurrency)
+                    }
+                    Ok(self.env().transferred_value())
+                }
+            }
+        }
+
+        // calls PSP22::transfer_from on the token contract
+        fn psp22_transfer_from(&self, token: AccountId, from: AccountId, to: AccountId, value: Balance
+        ) -> Result<(), Error> {
+            let result = build_call::<DefaultEnvironment>()
+                .call(token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::transfer_from")))
+                        .push_arg(from)
+                        .push_arg(to)
+                        .push_arg(value)
+                        .push_arg(Vec::<u8>::new())
+                )
+                .returns::<Result<(), Psp22Error>>()
+                .try_invoke();
+            match result {
+                Ok(Ok(Ok(()))) => Ok(()),
+                _ => Err(Error::TokenTransferFailed),
+            }
+        }
+
+        // calls PSP22::transfer on the token contract to pay out of this contract
+        fn psp22_transfer(&self, token: AccountId, to: AccountId, value: Balance) -> Result<(), Error> {
+            let result = build_call::<DefaultEnvironment>()
+                .call(token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("PSP22::transfer")))
+                        .push_arg(to)
+                        .push_arg(value)
+                        .push_arg(Vec::<u8>::new())
+                )
+                .returns::<Result<(), Psp22Error>>()
+                .try_invoke();
+            match result {
+                Ok(Ok(Ok(()))) => Ok(()),
+                _ => Err(Error::TokenTransferFailed),
+            }
+        }
+
+        // true if bid a goes before bid b: the lower bid goes first, and on equal
+        // bids the later arrival goes first so the earlier bidder keeps its slot
+        fn bid_before(a: &BidEntry, b: &BidEntry) -> bool {
+            a.bid < b.bid || (a.bid == b.bid && a.seq > b.seq)
+        }
+
+        // moves the entry at index i up the heap until its parent goes before it
+        fn heap_sift_up(heap: &mut [BidEntry], mut i: usize) {
+            while i > 0 {
+                let parent = (i - 1) / 2;
+                if !Self::bid_before(&heap[i], &heap[parent]) {
+                    break;
+                }
+                heap.swap(i, parent);
+                i = parent;
+            }
+        }
+
+        // moves the entry at index i down the heap until it goes before its children
+        fn heap_sift_down(heap: &mut [BidEntry], mut i: usize) {
+            loop {
+                let left = i.saturating_mul(2).saturating_add(1);
+                let right = left.saturating_add(1);
+                let mut first = i;
+                if left < heap.len() && Self::bid_before(&heap[left], &heap[first]) {
+                    first = left;
+                }
+                if right < heap.len() && Self::bid_before(&heap[right], &heap[first]) {
+                    first = right;
+                }
+                if first == i {
+                    break;
+                }
+                heap.swap(i, first);
+                i = first;
+            }
+        }
+
+        // adds an entry to the heap in O(log n)
+        fn heap_push(heap: &mut Vec<BidEntry>, entry: BidEntry) {
+            heap.push(entry);
+            let last = heap.len().saturating_sub(1);
+            Self::heap_sift_up(heap, last);
+        }
+
+        // takes the lowest bid off the heap in O(log n)
+        fn heap_pop(heap: &mut Vec<BidEntry>) -> Option<BidEntry> {
+            if heap.is_empty() {
+                return None
+            }
+            let last = heap.len().saturating_sub(1);
+            heap.swap(0, last);
+            let lowest = heap.pop();
+            Self::heap_sift_down(heap, 0);
+            lowest
+        }
+
+        // takes a given message off the heap, wherever it is
+        fn heap_remove(heap: &mut Vec<BidEntry>, message_id: Hash) -> Option<BidEntry> {
+            let i = heap.iter().position(|entry| entry.message_id == message_id)?;
+            let last = heap.len().saturating_sub(1);
+            heap.swap(i, last);
+            let removed = heap.pop();
+            if i < heap.len() {
+                Self::heap_sift_down(heap, i);
+                Self::heap_sift_up(heap, i);
+            }
+            removed
+        }
+
+        // removes a top level message and all of its replies from storage
+        fn remove_message(&mut self, message_id: Hash) {
+            // remove the message from the message_map
+            self.message_map.remove(message_id);
+            // remove all the replies to the message from the reply_map 
+            let replies = self.message_reply_map.get(message_id).unwrap_or_default();
+            for id in replies.messages.iter() {
+                self.reply_map.remove(id);
+            }
+            // remove the message from the message_reply_map
+            self.message_reply_map.remove(message_id);
+            // remove the message from the trending leaderboards
+            for window in 0..3u8 {
+                self.message_window_map.remove((message_id, window));
+                let mut board = self.trending_map.get(window).unwrap_or_default();
+                if board.entries.iter().any(|entry| entry.message_id == message_id) {
+                    board.entries.retain(|entry| entry.message_id != message_id);
+                    self.trending_map.insert(window, &board);
+                }
+            }
+            for endorser in self.trending_endorsers_map.get(message_id).unwrap_or_default().following {
+                self.trending_endorsement_map.remove((message_id, endorser));
+            }
+            self.trending_endorsers_map.remove(message_id);
+            // remove the message from the global timeline
+            if let Some(position) = self.global_feed_index_map.get(message_id) {
+                self.global_feed_index_map.remove(message_id);
+                let slot = position.checked_rem_euclid(500).unwrap_or_default();
+                self.global_feed_map.remove(slot);
+            }
+        }
+
+        // unpins the owner's pinned message and drops it if it is no longer recent
+        fn unpin(&mut self, owner: AccountId) {
+            if let Some(pinned) = self.account_pinned_map.get(owner) {
+                self.account_pinned_map.remove(owner);
+                let recent = self.account_messages_map.get(owner).unwrap_or_default().messages;
+                if !recent.contains(&pinned) {
+                    self.remove_message(pinned);
+                }
+                Self::env().emit_event(MessageUnpinned {
+                    from: owner,
+                    message_id: pinned,
+                });
+            }
+        }
+
+        // follows an account for the caller, or sends a follow request to a private account
+        fn try_follow(&mut self, caller: AccountId, follow: AccountId) -> Result<(), Error> {
+            // follow the account an old account id has migrated to
+            let follow = self.current_account(follow);
+            // Is this account already being followed? or is the caller trying to follow themselves?
+            if self.set_contains(FOLLOWING_SET, caller, follow) || caller == follow {
+                return Err(Error::CannotFollow);
+            }
+            self.mark_seen(caller);
+            // if the account is private, leave a follow request for them to approve
+            if self.account_settings_map.get(follow).unwrap_or_default().private {
+                let mut requests = self.account_follow_requests_map.get(follow).unwrap_or_default();
+                // a request is already waiting
+                if requests.following.contains(&caller) {
+                    return Err(Error::CannotFollow);
+                }
+                // the caller must have room to follow once the request is approved
+                if self.set_len(FOLLOWING_SET, caller) >= self.max_following
+                && !self.account_settings_map.get(caller).unwrap_or_default().evict_oldest_at_limit {
+                    return Err(Error::FollowLimitReached);
+                }
+                // keep at most 490 pending requests
+                if requests.following.len() > 489 {
+                    return Err(Error::FollowRequestsFull);
+                }
+                requests.following.push(caller);
+                self.account_follow_requests_map.insert(follow, &requests);
+
+                // Emit an event to register the follow request to the chain
+                Self::env().emit_event(FollowRequested {
+                    from: caller,
+                    requested_to_follow: follow,
+                });
+                return Ok(())
+            }
+            // Otherwise, update the following set for this caller
+            self.add_follow(caller, follow)
+        }
+
+        // unfollows an account the caller follows
+        fn try_unfollow(&mut self, caller: AccountId, unfollow: AccountId) -> Result<(), Error> {
+            // a follow of an old account id may already have moved to the account it migrated to
+            let unfollow = if self.set_contains(FOLLOWING_SET, caller, unfollow) {
+                unfollow
+            }
+            else {
+                self.current_account(unfollow)
+            };
+            // Is this account currently being followed? If TRUE, proceed...
+            if self.set_contains(FOLLOWING_SET, caller, unfollow) {
+                self.remove_follow(caller, unfollow);
+            }
+            // If the account is not currently being followed, ERROR: Already Not Following
+            else {
+                return Err(Error::NotFollowing);
+            }
+            Ok(())
+        }
+
+        // blocks an account for the caller
+        fn try_block(&mut self, caller: AccountId, block: AccountId) -> Result<(), Error> {
+            // block the account an old account id has migrated to
+            let block = self.current_account(block);
+            // Is this account already being blocked? OR is the blocked list full?
+            if self.set_contains(BLOCKED_SET, caller, block) || caller == block {
+                return Err(Error::CannotBlock);
+            }
+            // Otherwise, update the blocked set for this caller
+            else {
+                // if the blocked set is full, unblock the oldest or send an error
+                self.make_room(BLOCKED_SET, caller)?;
+                // add the new block to the set of accounts caller is blocking
+                self.block_or_mute(BLOCKED_SET, caller, block);
+
+                // Emit an event to register the block to the chain
+                Self::env().emit_event(NewBlock {
+                    from: caller,
+                    blocked: block,
+                });
+                
+            }
+            Ok(())
+        }
+
+        // unblocks an account the caller blocked
+        fn try_unblock(&mut self, caller: AccountId, unblock: AccountId) -> Result<(), Error> {
+            // a block of an old account id may already have moved to the account it migrated to
+            let unblock = if self.set_contains(BLOCKED_SET, caller, unblock) {
+                unblock
+            }
+            else {
+                self.current_account(unblock)
+            };
+            // Is this account currently being blocked? If TRUE, proceed...
+            if self.set_contains(BLOCKED_SET, caller, unblock) {
+                // remove the unblock from the set of accounts they are blocking
+                self.unblock_or_unmute(BLOCKED_SET, caller, unblock);
+
+                // Emit an event to register the unblock to the chain
+                Self::env().emit_event(NewUnBlock {
+                    from: caller,
+                    unblocked: unblock,
+                });
+                
+            }
+            // If the account is not currently being blocked, ERROR: Not Blocked
+            else {
+                return Err(Error::NotBlocked);
+            }
+            Ok(())
+        }
+
+        // adds follow to the follower's following set and updates the follower count
+        fn add_follow(&mut self, follower: AccountId, follow: AccountId) -> Result<(), Error> {
+            // if the following set is full, unfollow the oldest or send an error
+            self.make_room(FOLLOWING_SET, follower)?;
+            // add the new follow to the set of accounts the follower is following
+            self.set_insert(FOLLOWING_SET, follower, follow);
+            self.set_insert(FOLLOWERS_SET, follow, follower);
+            // get the number of current followers for the followed account
+            let mut current_followers = self.account_followers_map.get(follow).unwrap_or_default(); 
+            // add the follower to the count of followers for this account
+            current_followers = current_followers.saturating_add(1);
+            // Update (overwrite) the account_followers_map entry in the storage
+            self.account_followers_map.insert(follow, &current_followers);
+
+            // Emit an event to register the follow to the chain
+            Self::env().emit_event(NewFollow {
+                from: follower,
+                is_following: follow,
+            });
+            Ok(())
+        }
+
+        // makes room for one more account in the owner's following or blocked set.
+        // At the limit this is an error, unless the owner has chosen to have their
+        // oldest follow (or block) undone instead, which is done with the usual events.
+        fn make_room(&mut self, kind: u8, owner: AccountId) -> Result<(), Error> {
+            let (limit, error) = if kind == FOLLOWING_SET {
+                (self.max_following, Error::FollowLimitReached)
+            } else {
+                (self.max_blocked, Error::BlockLimitReached)
+            };
+            if self.set_len(kind, owner) < limit {
+                return Ok(())
+            }
+            if !self.account_settings_map.get(owner).unwrap_or_default().evict_oldest_at_limit {
+                return Err(error)
+            }
+            // the limit can be lowered below the size of the set, so loop
+            while self.set_len(kind, owner) >= limit {
+                let oldest = match self.set_oldest(kind, owner) {
+                    Some(oldest) => oldest,
+                    None => break,
+                };
+                if kind == FOLLOWING_SET {
+                    self.remove_follow(owner, oldest);
+                }
+                else {
+                    self.unblock_or_unmute(kind, owner, oldest);
+                    Self::env().emit_event(NewUnBlock {
+                        from: owner,
+                        unblocked: oldest,
+                    });
+                }
+            }
+            Ok(())
+        }
+
+        // gets the 3 most recent posts and the most recently elevated message of 
+        // each account, leaving out whatever private accounts hide from the viewer
+        fn collect_feed(&self, viewer: AccountId, accounts: &[AccountId]) -> Vec<MessageDetails> {
+            let mut message_list: Vec<MessageDetails> = Vec::new();
+            // iterate over the vector of AccountIds...
+            for account in accounts.iter() {
+                // for each AccountId, get the list of message_ids sent from that account
+                if self.can_view(viewer, *account) {
+                    let message_idvec = self.account_messages_map.get(account).unwrap_or_default().messages;
+                    // iterate over those messages to get the details for each
+                    for messageidhash in message_idvec.iter() {
+                        // get the details for that message
+                        let details = self.message_map.get(messageidhash).unwrap_or_default();
+                        // add the details to the message_list vector
+                        message_list.push(details);
+                        // replies on request by a different message function
+                    }
+                }
+                // then get the most recently elevated message by that account and get the details
+                let elevated_id = self.account_elevated_map.get(account).unwrap_or_default();
+                // get the details for that message
+                let details = self.message_map.get(elevated_id).unwrap_or_default();
+                // add the details to the message_list vector, unless it is hidden by a private account
+                if self.can_view(viewer, details.from_acct) {
+                    message_list.push(details);
+                }
+            }
+            message_list
+        }
+
+        // splits a list of interests into lowercase keywords on commas and whitespace
+        fn interest_keywords(interests: &[u8]) -> Vec<Vec<u8>> {
+            let mut keywords: Vec<Vec<u8>> = Vec::new();
+            for word in interests.split(|c| *c == b',' || c.is_ascii_whitespace()) {
+                let keyword = word.to_ascii_lowercase();
+                if !keyword.is_empty() && !keywords.contains(&keyword) {
+                    keywords.push(keyword);
+                }
+            }
+            keywords
+        }
+
+        // the length in milliseconds of each trending window (hour, day, week)
+        fn trending_window_length(window: u8) -> Option<u64> {
+            match window {
+                0 => Some(3600000),
+                1 => Some(86400000),
+                2 => Some(604800000),
+                _ => None,
+            }
+        }
+
+        // moves an endorsement window forward to the window that contains now
+        fn roll_window(entry: &mut EndorsementWindow, length: u64, now: u64) {
+            let start = now.saturating_sub(now.checked_rem_euclid(length).unwrap_or_default());
+            if start == entry.window_start {
+                return
+            }
+            // the old current window becomes the previous one only if they are adjacent
+            entry.previous = if entry.window_start.saturating_add(length) == start { entry.current } else { 0 };
+            entry.current = 0;
+            entry.window_start = start;
+        }
+
+        // endorsements in the current window plus the part of the previous window 
+        // that still overlaps a full window length ending now
+        fn trending_score(entry: &EndorsementWindow, length: u64, now: u64) -> u128 {
+            let mut rolled = entry.clone();
+            Self::roll_window(&mut rolled, length, now);
+            let elapsed = u128::from(now.saturating_sub(rolled.window_start));
+            let length = u128::from(length);
+            let weighted = rolled.current.saturating_mul(length)
+                .saturating_add(rolled.previous.saturating_mul(length.saturating_sub(elapsed)));
+            weighted.checked_div(length).unwrap_or_default()
+        }
+
+        // counts a new endorsement for the message in every trending window and 
+        // keeps each leaderboard to the 50 highest scoring messages
+        fn record_trending(&mut self, message_id: Hash) {
+            let now = self.env().block_timestamp();
+            for window in 0..3u8 {
+                let length = Self::trending_window_length(window).unwrap_or(1);
+                let mut entry = self.message_window_map.get((message_id, window)).unwrap_or_default();
+                entry.message_id = message_id;
+                Self::roll_window(&mut entry, length, now);
+                entry.current = entry.current.saturating_add(1);
+                self.message_window_map.insert((message_id, window), &entry);
+
+                // update the leaderboard for this window
+                let mut board = self.trending_map.get(window).unwrap_or_default();
+                board.entries.retain(|value| value.message_id != message_id);
+                board.entries.push(entry);
+                if board.entries.len() > 50 {
+                    // kick out the lowest score
+                    let mut low_index: usize = 0;
+                    let mut low_score: u128 = u128::MAX;
+                    for (i, value) in board.entries.iter().enumerate() {
+                        let score = Self::trending_score(value, length, now);
+                        if score < low_score {
+                            low_score = score;
+                            low_index = i;
+                        }
+                    }
+                    board.entries.remove(low_index);
+                }
+                self.trending_map.insert(window, &board);
+            }
+        }
+
+        // removes followed from the follower's following set and updates the follower count
+        fn remove_follow(&mut self, follower: AccountId, followed: AccountId) {
+            // remove the followed account from the set of accounts they are following
+            self.set_remove(FOLLOWING_SET, follower, followed);
+            self.set_remove(FOLLOWERS_SET, followed, follower);
+
+            // reduce the followers count for the followed account...
+            let mut current_followers = self.account_followers_map.get(followed).unwrap_or_default();
+            current_followers = current_followers.saturating_sub(1);
+            // update (overwrite) the account_followers_map entry in the storage
+            self.account_followers_map.insert(followed, &current_followers);
+
+            // Emit an event to register the unfollow to the chain
+            Self::env().emit_event(NewUnFollow {
+                from: follower,
+                stopped_following: followed,
+            });
+        }
+
+        // true if the account has no social data that a migration could overwrite
+        // (an account that has migrated away never counts as empty)
+        fn is_empty_account(&self, account: AccountId) -> bool {
+            !self.migrated_to_map.contains(account)
+            && !self.account_settings_map.contains(account)
+            && !self.account_profile_map.contains(account)
+            && !self.account_messages_map.contains(account)
+            && !self.account_paid_messages_map.contains(account)
+            && !self.account_lists_map.contains(account)
+            && self.set_len(FOLLOWING_SET, account) == 0
+            && self.set_len(FOLLOWERS_SET, account) == 0
+            && self.set_len(BLOCKED_SET, account) == 0
+            && self.set_len(MUTED_SET, account) == 0
+        }
+
+        // moves every per-account entry from the old account to the (empty) new account
+        fn migrate_account(&mut self, old: AccountId, new: AccountId) {
+            // SETTINGS, USERNAME AND PROFILE
+            if let Some(settings) = self.account_settings_map.get(old) {
+                // repoint the username
+                if !settings.username.is_empty() && self.username_map.get(&settings.username) == Some(old) {
+                    self.username_map.insert(&settings.username, &new);
+                }
+                // repoint the interest keyword indexes
+                for keyword in Self::interest_keywords(&settings.interests).iter() {
+                    let mut accounts = self.interest_accounts_map.get(keyword).unwrap_or_default();
+                    for account in accounts.following.iter_mut() {
+                        if *account == old {
+                            *account = new;
+                        }
+                    }
+                    self.interest_accounts_map.insert(keyword, &accounts);
+                }
+                self.account_settings_map.insert(new, &settings);
+                self.account_settings_map.remove(old);
+            }
+            if let Some(profile) = self.account_profile_map.get(old) {
+                self.account_profile_map.insert(new, &profile);
+                self.account_profile_map.remove(old);
+            }
+
+            // FOLLOWS, FOLLOWERS (and their counts), BLOCKS AND MUTES are moved in
+            // batches by migrate_graph_batch
+            // the account keeps the age and paid endorsement history of the old account
+            if let Some(first_seen) = self.account_first_seen_map.get(old) {
+                self.account_first_seen_map.insert(new, &first_seen);
+                self.account_first_seen_map.remove(old);
+            }
+            if let Some(history) = self.endorsement_history_map.get(old) {
+                self.endorsement_history_map.insert(new, &history);
+                self.endorsement_history_map.remove(old);
+            }
+            if let Some(requests) = self.account_follow_requests_map.get(old) {
+                self.account_follow_requests_map.insert(new, &requests);
+                self.account_follow_requests_map.remove(old);
+            }
+
+            // MESSAGES, PINNED AND ELEVATED
+            let messages = self.account_messages_map.get(old).unwrap_or_default();
+            let pinned = self.account_pinned_map.get(old);
+            let mut message_ids = messages.messages.clone();
+            if let Some(pinned_id) = pinned {
+                if !message_ids.contains(&pinned_id) {
+                    message_ids.push(pinned_id);
+                }
+                self.account_pinned_map.insert(new, &pinned_id);
+                self.account_pinned_map.remove(old);
+            }
+            for id in message_ids.iter() {
+                if let Some(mut details) = self.message_map.get(id) {
+                    details.from_acct = new;
+                    self.message_map.insert(id, &details);
+                }
+            }
+            if self.account_messages_map.contains(old) {
+                self.account_messages_map.insert(new, &messages);
+                self.account_messages_map.remove(old);
+            }
+            if let Some(elevated) = self.account_elevated_map.get(old) {
+                self.account_elevated_map.insert(new, &elevated);
+                self.account_elevated_map.remove(old);
+            }
+
+            // PAID MESSAGES (so that refunds go to the new account)
+            if let Some(paid_messages) = self.account_paid_messages_map.get(old) {
+                for id in paid_messages.messages.iter() {
+                    if let Some(mut details) = self.paid_message_map.get(id) {
+                        details.from_acct = new;
+                        for endorser in details.endorsers.iter_mut() {
+                            if *endorser == old {
+                                *endorser = new;
+                            }
+                        }
+                        self.paid_message_map.insert(id, &details);
+                    }
+                }
+                self.account_paid_messages_map.insert(new, &paid_messages);
+                self.account_paid_messages_map.remove(old);
+            }
+
+            // CLAIMABLE REFUNDS
+            if let Some(refund) = self.claimable_refunds_map.get(old) {
+                let claimable = self.claimable_refunds_map.get(new).unwrap_or_default();
+                self.claimable_refunds_map.insert(new, &claimable.saturating_add(refund));
+                self.claimable_refunds_map.remove(old);
+            }
+
+            // PENDING EARNINGS
+            if let Some(earned) = self.pending_earnings_map.get(old) {
+                let pending = self.pending_earnings_map.get(new).unwrap_or_default();
+                self.pending_earnings_map.insert(new, &pending.saturating_add(earned));
+                self.pending_earnings_map.remove(old);
+            }
+
+            // TOKEN REFUNDS AND EARNINGS (in every token the old account holds, even
+            // tokens that have since been taken off the allow list)
+            for token in self.account_tokens_map.get(old).unwrap_or_default() {
+                self.add_account_token(new, token);
+                if let Some(refund) = self.token_refunds_map.get((token, old)) {
+                    let claimable = self.token_refunds_map.get((token, new)).unwrap_or_default();
+                    self.token_refunds_map.insert((token, new), &claimable.saturating_add(refund));
+                    self.token_refunds_map.remove((token, old));
                 }
+                if let Some(earned) = self.token_earnings_map.get((token, old)) {
+                    let pending = self.token_earnings_map.get((token, new)).unwrap_or_default();
+                    self.token_earnings_map.insert((token, new), &pending.saturating_add(earned));
+                    self.token_earnings_map.remove((token, old));
+                }
+            }
+            self.account_tokens_map.remove(old);
+
+            // FOLLOW LISTS
+            if let Some(lists) = self.account_lists_map.get(old) {
+                for id in lists.messages.iter() {
+                    if let Some(mut list) = self.list_map.get(id) {
+                        list.owner = new;
+                        self.list_map.insert(id, &list);
+                    }
+                }
+                self.account_lists_map.insert(new, &lists);
+                self.account_lists_map.remove(old);
+            }
+        }
 
-                // EMIT EVENT to register the post to the chain
-                Self::env().emit_event(MessageBroadcast {
-                    from: Self::env().caller(),
-                    message: new_message_clone2,
-                    message_id: new_message_id,
-                    link: link_clone,
-                    link2: link2_clone,
-                    reply_to: replying_to,
-                    timestamp: self.env().block_timestamp()
+        // moves up to max_items follows, followers, blocks and mutes from the old account
+        // to the new one (both sides of each follow are moved, along with the follower
+        // counts), repoints other accounts' blocks and mutes of the old account, and
+        // finishes the migration once the old account has none left
+        fn migrate_graph_batch(&mut self, old: AccountId, new: AccountId, max_items: u32) {
+            let mut moved: u32 = 0;
+            for kind in GRAPH_SETS {
+                while moved < max_items {
+                    let account = match self.set_oldest(kind, old) {
+                        Some(account) => account,
+                        None => break,
+                    };
+                    moved = moved.saturating_add(1);
+                    // the old account's own blocks and mutes
+                    if kind == BLOCKED_SET || kind == MUTED_SET {
+                        self.unblock_or_unmute(kind, old, account);
+                        if account != new {
+                            self.block_or_mute(kind, new, account);
+                        }
+                        continue;
+                    }
+                    // another account's block or mute of the old account
+                    if kind == BLOCKED_BY_SET || kind == MUTED_BY_SET {
+                        let blocking_kind = if kind == BLOCKED_BY_SET { BLOCKED_SET } else { MUTED_SET };
+                        self.unblock_or_unmute(blocking_kind, account, old);
+                        if account != new {
+                            self.block_or_mute(blocking_kind, account, new);
+                        }
+                        continue;
+                    }
+                    self.set_remove(kind, old, account);
+                    if kind == FOLLOWING_SET {
+                        self.set_remove(FOLLOWERS_SET, account, old);
+                        self.change_follower_count(account, false);
+                    }
+                    if kind == FOLLOWERS_SET {
+                        self.set_remove(FOLLOWING_SET, account, old);
+                        self.change_follower_count(old, false);
+                    }
+                    // skip follows the new account already has (made since the migration began)
+                    if account != new && !self.set_contains(kind, new, account) {
+                        self.set_insert(kind, new, account);
+                        if kind == FOLLOWING_SET {
+                            self.set_insert(FOLLOWERS_SET, account, new);
+                            self.change_follower_count(account, true);
+                        }
+                        if kind == FOLLOWERS_SET {
+                            self.set_insert(FOLLOWING_SET, account, new);
+                            self.change_follower_count(new, true);
+                        }
+                    }
+                }
+            }
+            let finished = GRAPH_SETS.iter().all(|kind| self.set_len(*kind, old) == 0);
+            if finished {
+                self.migration_in_progress_map.remove(new);
+                // Emit an event so followers and indexers can update
+                Self::env().emit_event(AccountMigrated {
+                    from: old,
+                    to: new,
                 });
+            }
+        }
+
+        // adds account to the owner's blocked (or muted) set, and the owner to
+        // the account's blocked by (or muted by) set
+        fn block_or_mute(&mut self, kind: u8, owner: AccountId, account: AccountId) {
+            let by_kind = if kind == BLOCKED_SET { BLOCKED_BY_SET } else { MUTED_BY_SET };
+            self.set_insert(kind, owner, account);
+            self.set_insert(by_kind, account, owner);
+        }
+
+        // removes account from the owner's blocked (or muted) set, and the owner
+        // from the account's blocked by (or muted by) set
+        fn unblock_or_unmute(&mut self, kind: u8, owner: AccountId, account: AccountId) {
+            let by_kind = if kind == BLOCKED_SET { BLOCKED_BY_SET } else { MUTED_BY_SET };
+            self.set_remove(kind, owner, account);
+            self.set_remove(by_kind, account, owner);
+        }
 
+        // adds one to (or takes one from) the account's follower count
+        fn change_follower_count(&mut self, account: AccountId, add: bool) {
+            let followers = self.account_followers_map.get(account).unwrap_or_default();
+            if add {
+                self.account_followers_map.insert(account, &followers.saturating_add(1));
             }
             else {
-                // if the replying_to message hash does not exist, send an error
-                return Err(Error::ReplyingToMessageDoesNotExist)
+                self.account_followers_map.insert(account, &followers.saturating_sub(1));
             }
-            
-            Ok(())
         }
 
-
-        // >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
-        // >>>>>>>>>>>>>>>>>>>>>>>>>> SECONDARY GET MESSAGES <<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<<
-        // >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
-
-        // 🟢 13 VERIFY THAT AN ACCOUNT HAS UPDATED THEIR SETTINGS AT LEAST ONCE 
-        #[ink(message)]
-        pub fn verify_account(&self, verify: AccountId) -> u8 {
-            let mut result: u8 = 0;
-            if self.account_settings_map.contains(verify) {
-                result = 1;
+        // the account an old account id has migrated to (following every migration
+        // since), or the account itself if it never migrated
+        fn current_account(&self, account: AccountId) -> AccountId {
+            let mut current = account;
+            for _ in 0..10 {
+                match self.migrated_to_map.get(current) {
+                    Some(next) => current = next,
+                    None => break,
+                }
             }
-            result
+            current
         }
 
-        // 🟢 14 GET ACCOUNT PAID MESSAGES
-        // given an accountId, returns the details of stored paid messages sent by that account
-        #[ink(message)]
-        pub fn get_account_paid_messages(&self, user: AccountId) -> Vec<PaidMessageDetails> {
-            let message_idvec = self.account_paid_messages_map.get(&user).unwrap_or_default().messages;
-            let mut message_list: Vec<PaidMessageDetails> = Vec::new();
-            for messageidhash in message_idvec.iter() {
-                // get the details for that message
-                let details = self.paid_message_map.get(&messageidhash).unwrap_or_default();
-                // add the details to the message_list vector
-                message_list.push(details);
+        // true if the account, or any account it migrated from or to, has already
+        // endorsed the paid message, so a migrated key cannot be paid twice
+        fn has_endorsed(&self, details: &PaidMessageDetails, account: AccountId) -> bool {
+            if details.endorsers.contains(&account) {
+                return true;
             }
-            // return the vector of message details
-            message_list
+            let mut previous = account;
+            for _ in 0..10 {
+                match self.migrated_from_map.get(previous) {
+                    Some(older) if details.endorsers.contains(&older) => return true,
+                    Some(older) => previous = older,
+                    None => break,
+                }
+            }
+            let mut next = account;
+            for _ in 0..10 {
+                match self.migrated_to_map.get(next) {
+                    Some(newer) if details.endorsers.contains(&newer) => return true,
+                    Some(newer) => next = newer,
+                    None => break,
+                }
+            }
+            false
         }
 
-        // 🟢 15 get the stored vector of accounts followed by a given AccountId
-        #[ink(message)]
-        pub fn get_account_following(&self, user: AccountId) -> Vec<AccountId> {
-            self.account_following_map.get(&user).unwrap_or_default().following
+        // true if the account is in the owner's set of the given kind
+        fn set_contains(&self, kind: u8, owner: AccountId, account: AccountId) -> bool {
+            self.account_set_position_map.contains((kind, owner, account))
         }
 
-        // 🟢 16 Get the stored details on a paid message post, given the message_id hash.  
-        #[ink(message)]
-        pub fn get_details_for_paid_message(&self, message_id: Hash
-        ) -> PaidMessageDetails {
+        // the number of accounts in the owner's set of the given kind
+        fn set_len(&self, kind: u8, owner: AccountId) -> u32 {
+            self.account_set_meta_map.get((kind, owner)).unwrap_or_default().len
+        }
 
-            // get the details for this message
-            let details = self.paid_message_map.get(&message_id).unwrap_or_default();
-            // return the restuls
-            details
+        // every account in the owner's set of the given kind, oldest first
+        fn set_members(&self, kind: u8, owner: AccountId) -> Vec<AccountId> {
+            let mut members: Vec<AccountId> = Vec::new();
+            for page in self.account_set_meta_map.get((kind, owner)).unwrap_or_default().pages {
+                let mut accounts = self.account_set_page_map.get((kind, owner, page)).unwrap_or_default().accounts;
+                members.append(&mut accounts);
+            }
+            members
         }
 
-        // 🟢 17 Get the stored details on a public message post, given the message_id hash.  
-        #[ink(message)]
-        pub fn get_details_for_message(&self, message_id: Hash
-        ) -> MessageDetails {
+        // the account that has been in the owner's set of the given kind the longest
+        fn set_oldest(&self, kind: u8, owner: AccountId) -> Option<AccountId> {
+            let first_page = *self.account_set_meta_map.get((kind, owner)).unwrap_or_default().pages.first()?;
+            self.account_set_page_map.get((kind, owner, first_page)).unwrap_or_default().accounts.first().copied()
+        }
 
-            // get the details for this message
-            let details = self.message_map.get(&message_id).unwrap_or_default();
-            // return the results
-            details
+        // adds an account to the last page of the owner's set, pages hold 50 accounts
+        fn set_insert(&mut self, kind: u8, owner: AccountId, account: AccountId) {
+            if self.set_contains(kind, owner, account) {
+                return
+            }
+            let mut meta = self.account_set_meta_map.get((kind, owner)).unwrap_or_default();
+            let mut page_id = meta.next_page;
+            let mut page = AccountPage::default();
+            if let Some(last) = meta.pages.last() {
+                let last_page = self.account_set_page_map.get((kind, owner, *last)).unwrap_or_default();
+                if last_page.accounts.len() < 50 {
+                    page_id = *last;
+                    page = last_page;
+                }
+            }
+            // start a new page when the last one is full
+            if page_id == meta.next_page {
+                meta.pages.push(page_id);
+                meta.next_page = meta.next_page.saturating_add(1);
+            }
+            page.accounts.push(account);
+            meta.len = meta.len.saturating_add(1);
+            self.account_set_page_map.insert((kind, owner, page_id), &page);
+            self.account_set_position_map.insert((kind, owner, account), &page_id);
+            self.account_set_meta_map.insert((kind, owner), &meta);
         }
 
-        // 🟢 18 Get all the replies to a single message_id hash.
-        #[ink(message)]
-        pub fn get_replies_for_message(&self, message_id: Hash
-        ) -> Vec<MessageDetails> {
-            // set up the results vector
-            let mut results: Vec<MessageDetails> = Vec::new();
-            // get the replies for this message
-            let replies = self.message_reply_map.get(&message_id).unwrap_or_default();
-            // get the details
-            for id in replies.messages.iter() {
-                let details = self.reply_map.get(id).unwrap_or_default();
-                results.push(details);
+        // removes an account from the owner's set, dropping its page if it empties
+        fn set_remove(&mut self, kind: u8, owner: AccountId, account: AccountId) {
+            let page_id = match self.account_set_position_map.get((kind, owner, account)) {
+                Some(page_id) => page_id,
+                None => return,
+            };
+            let mut meta = self.account_set_meta_map.get((kind, owner)).unwrap_or_default();
+            let mut page = self.account_set_page_map.get((kind, owner, page_id)).unwrap_or_default();
+            page.accounts.retain(|value| *value != account);
+            if page.accounts.is_empty() {
+                self.account_set_page_map.remove((kind, owner, page_id));
+                meta.pages.retain(|value| *value != page_id);
+            }
+            else {
+                self.account_set_page_map.insert((kind, owner, page_id), &page);
+            }
+            self.account_set_position_map.remove((kind, owner, account));
+            meta.len = meta.len.saturating_sub(1);
+            if meta.len == 0 {
+                self.account_set_meta_map.remove((kind, owner));
+            }
+            else {
+                self.account_set_meta_map.insert((kind, owner), &meta);
             }
-            // return the results
-            results
         }
 
+        // true if the viewer may see the author's posts, the posts of a private
+        // account are only visible to the account itself and its approved followers
+        fn can_view(&self, viewer: AccountId, author: AccountId) -> bool {
+            if viewer == author || !self.account_settings_map.get(author).unwrap_or_default().private {
+                return true
+            }
+            self.set_contains(FOLLOWING_SET, viewer, author)
+        }
 
-        // >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
-        // >>>>>>>>>>>>>>>>>> REWARD PROGRAM MESSAGES >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
-        // >>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>
+        // gives a (non empty) username to the owner and releases their old one
+        fn claim_name(&mut self, owner: AccountId, name: Vec<u8>) -> Result<(), Error> {
+            // if the owner already holds this name there is nothing to do
+            if self.username_map.get(&name) == Some(owner) {
+                return Ok(())
+            }
+            // if the name exists in the the username map, it is taken
+            if self.username_map.contains(&name) {
+                return Err(Error::UsernameTaken)
+            }
+            // a released name is held back from everyone but its last owner for 7 days
+            if let Some(released) = self.released_username_map.get(&name) {
+                let time_since_release = self.env().block_timestamp().saturating_sub(released.released_at);
+                if released.released_by != owner && time_since_release < 604800000 {
+                    return Err(Error::UsernameInCooldown)
+                }
+            }
 
+            // give up the old name first, then register the new one
+            self.release_name(owner);
+            self.username_map.insert(&name, &owner);
+            self.released_username_map.remove(&name);
+            let mut settings = self.account_settings_map.get(owner).unwrap_or_default();
+            settings.username = name.clone();
+            self.account_settings_map.insert(owner, &settings);
+
+            Self::env().emit_event(UsernameClaimed {
+                from: owner,
+                username: name,
+            });
+            Ok(())
+        }
 
-        // 🟢 19 Rewards - Set Or Update Reward Root Account [RESTRICTED: ROOT]
-        #[ink(message)]
-        pub fn set_reward_root(&mut self, newroot: AccountId) -> Result<(), Error> {
-            let caller = Self::env().caller();
-            // if the root is already set, send an error
-            if self.reward_root_set != 1 || self.reward_root == caller {
-                // proceed - set the roots and update the storage
-                self.reward_root = newroot;
-                self.reward_root_set = 1;
+        // releases the owner's username (if any) and starts its cooldown
+        fn release_name(&mut self, owner: AccountId) {
+            let mut settings = self.account_settings_map.get(owner).unwrap_or_default();
+            let oldname = settings.username;
+            if oldname.is_empty() {
+                return
             }
-            else {
-                // error PermissionDenied
-                return Err(Error::PermissionDenied)
+            // only release the name from the username map if it really belongs to the owner
+            if self.username_map.get(&oldname) == Some(owner) {
+                self.username_map.remove(&oldname);
+                let released = ReleasedUsername {
+                    released_by: owner,
+                    released_at: self.env().block_timestamp(),
+                };
+                self.released_username_map.insert(&oldname, &released);
+                Self::env().emit_event(UsernameReleased {
+                    from: owner,
+                    username: oldname,
+                });
             }
+            settings.username = Vec::new();
+            self.account_settings_map.insert(owner, &settings);
+        }
 
-            Ok(())
+        // END OF MESSAGE LIST
+
+    }
+    // END OF CONTRACT STORAGE
+
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn id(n: u8) -> Hash {
+            Hash::from([n; 32])
         }
 
+        fn entry(n: u8, bid: Balance, seq: u64) -> BidEntry {
+            BidEntry { message_id: id(n), bid, seq }
+        }
 
-        // 🟢 20 Rewards - Set/Update Reward Interval and Amount [RESTRICTED: ROOT]
-        // Reward coin will be given to the account that makes the Xth claim in the system
-        #[ink(message)]
-        pub fn set_reward(&mut self, on: u8, interval: u128, amount: Balance) -> Result<(), Error> {
-            let caller = Self::env().caller();
-            if self.reward_root == caller {
-                // proceed to set the reward program paramteters
-                self.reward_on = on;
-                self.reward_interval = interval;
-                self.reward_amount = amount;
+        // pops every entry, returning the message ids in the order they came off
+        fn drain(heap: &mut Vec<BidEntry>) -> Vec<Hash> {
+            let mut order: Vec<Hash> = Vec::new();
+            while let Some(lowest) = ContractStorage::heap_pop(heap) {
+                order.push(lowest.message_id);
             }
-            else {
-                // error PermissionDenied
-                return Err(Error::PermissionDenied)
+            order
+        }
+
+        #[test]
+        fn heap_pops_lowest_bid_first() {
+            let mut heap: Vec<BidEntry> = Vec::new();
+            for (n, bid) in [(1, 50), (2, 10), (3, 40), (4, 30), (5, 20)] {
+                ContractStorage::heap_push(&mut heap, entry(n, bid, u64::from(n)));
             }
-            
-            Ok(())
+            assert_eq!(heap[0].message_id, id(2));
+            assert_eq!(drain(&mut heap), vec![id(2), id(5), id(4), id(3), id(1)]);
+            assert_eq!(ContractStorage::heap_pop(&mut heap), None);
         }
 
-        // 🟢 21 ADD COIN TO REWARD ACCOUNT [RESTRICTED: ROOT]
-        #[ink(message, payable)]
-        pub fn add_reward_balance(&mut self) -> Result<(), Error> {
-            let caller = Self::env().caller();
-            if self.reward_root == caller {
-                // add the paid in value to the reward_balance
-                let staked: Balance = self.env().transferred_value();
-                let newbalance: Balance = self.reward_balance.saturating_add(staked);
-                self.reward_balance = newbalance;
+        #[test]
+        fn heap_pops_later_arrival_first_on_equal_bids() {
+            let mut heap: Vec<BidEntry> = Vec::new();
+            ContractStorage::heap_push(&mut heap, entry(1, 10, 1));
+            ContractStorage::heap_push(&mut heap, entry(2, 10, 2));
+            ContractStorage::heap_push(&mut heap, entry(3, 10, 3));
+            ContractStorage::heap_push(&mut heap, entry(4, 5, 4));
+            assert_eq!(drain(&mut heap), vec![id(4), id(3), id(2), id(1)]);
+        }
+
+        #[test]
+        fn heap_remove_from_the_middle_keeps_order() {
+            let mut heap: Vec<BidEntry> = Vec::new();
+            for n in 1..=7u8 {
+                ContractStorage::heap_push(&mut heap, entry(n, Balance::from(n) * 10, u64::from(n)));
             }
-            else {
-                // error PermissionDenied
-                return Err(Error::PermissionDenied)
+            let removed = ContractStorage::heap_remove(&mut heap, id(3));
+            assert_eq!(removed, Some(entry(3, 30, 3)));
+            assert_eq!(ContractStorage::heap_remove(&mut heap, id(3)), None);
+            assert_eq!(drain(&mut heap), vec![id(1), id(2), id(4), id(5), id(6), id(7)]);
+        }
+
+        #[test]
+        fn heap_remove_last_and_only_entries() {
+            let mut heap: Vec<BidEntry> = Vec::new();
+            ContractStorage::heap_push(&mut heap, entry(1, 10, 1));
+            ContractStorage::heap_push(&mut heap, entry(2, 20, 2));
+            assert_eq!(ContractStorage::heap_remove(&mut heap, id(2)), Some(entry(2, 20, 2)));
+            assert_eq!(ContractStorage::heap_remove(&mut heap, id(1)), Some(entry(1, 10, 1)));
+            assert!(heap.is_empty());
+        }
+
+        fn account(n: u8) -> AccountId {
+            AccountId::from([n; 32])
+        }
+
+        #[ink::test]
+        fn set_insert_fills_pages_of_fifty() {
+            let mut contract = ContractStorage::new();
+            let owner = account(0);
+            for n in 1..=120u8 {
+                contract.set_insert(FOLLOWING_SET, owner, account(n));
             }
-            
-            Ok(())
+            // inserting an account twice does nothing
+            contract.set_insert(FOLLOWING_SET, owner, account(1));
+            let meta = contract.account_set_meta_map.get((FOLLOWING_SET, owner)).unwrap_or_default();
+            assert_eq!(meta.len, 120);
+            assert_eq!(meta.pages, vec![0, 1, 2]);
+            let members = contract.set_members(FOLLOWING_SET, owner);
+            assert_eq!(members, (1..=120u8).map(account).collect::<Vec<AccountId>>());
+            assert!(contract.set_contains(FOLLOWING_SET, owner, account(77)));
+            assert!(!contract.set_contains(BLOCKED_SET, owner, account(77)));
         }
 
+        #[ink::test]
+        fn set_remove_drops_empty_pages_and_keeps_oldest() {
+            let mut contract = ContractStorage::new();
+            let owner = account(0);
+            for n in 1..=60u8 {
+                contract.set_insert(FOLLOWING_SET, owner, account(n));
+            }
+            // emptying the first page moves the oldest member to the second page
+            for n in 1..=50u8 {
+                assert_eq!(contract.set_oldest(FOLLOWING_SET, owner), Some(account(n)));
+                contract.set_remove(FOLLOWING_SET, owner, account(n));
+            }
+            let meta = contract.account_set_meta_map.get((FOLLOWING_SET, owner)).unwrap_or_default();
+            assert_eq!(meta.pages, vec![1]);
+            assert_eq!(contract.set_len(FOLLOWING_SET, owner), 10);
+            assert_eq!(contract.set_oldest(FOLLOWING_SET, owner), Some(account(51)));
+            // removing an account that is not in the set does nothing
+            contract.set_remove(FOLLOWING_SET, owner, account(1));
+            assert_eq!(contract.set_len(FOLLOWING_SET, owner), 10);
+        }
 
-        // 🟢 22 RETRIEVE COIN FROM REWARD ACCOUNT [RESTRICTED: ROOT]
-        // turns reward program off and returns funds to the root
-        #[ink(message)]
-        pub fn shut_down_reward(&mut self) -> Result<(), Error> {
-            let caller = Self::env().caller();
-            if self.reward_root == caller {
-                // set the reward program to off
-                self.reward_on = 0;
-                // refund the coin to the reward root
-                // Check that there is a nonzero balance on the contract > existential deposit
-                if self.env().balance() > 10 && self.reward_balance > 0 {
-                    // pay the root the reward_balance minus 10
-                    let payout: Balance = self.reward_balance.saturating_sub(10);
-                    if self.env().transfer(caller, payout).is_err() {
-                        return Err(Error::PayoutFailed);
-                    }
-                }
-                // if the balance is < 10, Error (ZeroBalance)
-                else {
-                    return Err(Error::ZeroBalance);
-                }
+        #[ink::test]
+        fn set_reuses_storage_after_it_empties() {
+            let mut contract = ContractStorage::new();
+            let owner = account(0);
+            for n in 1..=3u8 {
+                contract.set_insert(MUTED_SET, owner, account(n));
             }
-            else {
-                // error PermissionDenied
-                return Err(Error::PermissionDenied)
+            for n in 1..=3u8 {
+                contract.set_remove(MUTED_SET, owner, account(n));
             }
-            
-            Ok(())
+            assert!(!contract.account_set_meta_map.contains((MUTED_SET, owner)));
+            assert_eq!(contract.set_oldest(MUTED_SET, owner), None);
+            assert!(contract.set_members(MUTED_SET, owner).is_empty());
+            // an emptied set starts again from its first page
+            contract.set_insert(MUTED_SET, owner, account(9));
+            let meta = contract.account_set_meta_map.get((MUTED_SET, owner)).unwrap_or_default();
+            assert_eq!(meta.pages, vec![0]);
+            assert_eq!(meta.len, 1);
+            assert_eq!(contract.set_members(MUTED_SET, owner), vec![account(9)]);
         }
 
+        fn set_caller(caller: AccountId) {
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+        }
 
-        // 🟢 23 GET CURRENT REWARD BALANCE AND SETTINGS [RESTRICTED: ROOT]
-        #[ink(message)]
-        pub fn get_reward_settings(&self) -> RewardSettings {
-            let caller = Self::env().caller();
-            let mut results = RewardSettings::default();
-            if self.reward_root == caller {
-                let settings = RewardSettings {
-                    reward_on: self.reward_on,
-                    reward_root_set: self.reward_root_set,
-                    reward_root: self.reward_root,
-                    reward_interval: self.reward_interval,
-                    reward_amount: self.reward_amount,
-                    reward_balance: self.reward_balance,
-                    reward_payouts: self.reward_payouts,
-                    claim_counter: self.claim_counter,
-                };
-                results = settings;
+        // starts and accepts a migration from old to new, moving the first 100 items
+        fn migrate(contract: &mut ContractStorage, old: AccountId, new: AccountId) {
+            set_caller(old);
+            assert_eq!(contract.initiate_account_migration(new), Ok(()));
+            set_caller(new);
+            assert_eq!(contract.accept_account_migration(old), Ok(()));
+        }
+
+        // sends a paid message from the advertiser that pays for endorsements by cat lovers
+        fn send_cat_ad(contract: &mut ContractStorage, advertiser: AccountId) -> Hash {
+            set_caller(advertiser);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(contract.send_paid_message_public(b"ad".to_vec(), Vec::new(), Vec::new(), 5, 100, b"cats".to_vec()), Ok(()));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+            contract.account_paid_messages_map.get(advertiser).unwrap_or_default().messages[0]
+        }
+
+        #[ink::test]
+        fn migration_accept_then_continue_moves_everything() {
+            let mut contract = ContractStorage::new();
+            let (old, new) = (account(1), account(2));
+            for n in 10..160u8 {
+                assert_eq!(contract.add_follow(account(n), old), Ok(()));
+            }
+            for n in 3..8u8 {
+                assert_eq!(contract.add_follow(old, account(n)), Ok(()));
             }
+            set_caller(account(200));
+            assert_eq!(contract.block_account(old), Ok(()));
+            set_caller(account(201));
+            assert_eq!(contract.mute_account(old), Ok(()));
+
+            // accepting moves the first 100 items, the rest wait for continue
+            migrate(&mut contract, old, new);
+            assert_eq!(contract.migration_in_progress_map.get(new), Some(old));
+            assert_eq!(contract.set_len(FOLLOWERS_SET, new), 95);
+            assert_eq!(contract.account_followers_map.get(new), Some(95));
+            assert_eq!(contract.account_followers_map.get(old), Some(55));
+
+            assert_eq!(contract.continue_account_migration(100), Ok(()));
+            assert!(!contract.migration_in_progress_map.contains(new));
+            assert_eq!(contract.continue_account_migration(100), Err(Error::NoPendingMigration));
+            for kind in GRAPH_SETS {
+                assert_eq!(contract.set_len(kind, old), 0);
+            }
+            assert_eq!(contract.set_len(FOLLOWING_SET, new), 5);
+            assert!(contract.set_contains(FOLLOWERS_SET, account(3), new));
+            assert_eq!(contract.set_len(FOLLOWERS_SET, new), 150);
+            assert_eq!(contract.account_followers_map.get(new), Some(150));
+            assert_eq!(contract.account_followers_map.get(old), Some(0));
+            assert!(contract.set_contains(FOLLOWING_SET, account(159), new));
+            // other accounts' blocks and mutes of the old account now apply to the new one
+            assert!(contract.set_contains(BLOCKED_SET, account(200), new));
+            assert!(!contract.set_contains(BLOCKED_SET, account(200), old));
+            assert_eq!(contract.set_members(BLOCKED_BY_SET, new), vec![account(200)]);
+            assert!(contract.set_contains(MUTED_SET, account(201), new));
+            assert_eq!(contract.set_members(MUTED_BY_SET, new), vec![account(201)]);
+        }
 
-            results
+        #[ink::test]
+        fn follows_during_migration_keep_follower_counts() {
+            let mut contract = ContractStorage::new();
+            let (old, new) = (account(1), account(2));
+            for n in 10..130u8 {
+                assert_eq!(contract.add_follow(account(n), old), Ok(()));
+            }
+            migrate(&mut contract, old, new);
+            assert!(contract.migration_in_progress_map.contains(new));
+
+            // a follower that has already moved and one that has not both unfollow the old id
+            set_caller(account(10));
+            assert_eq!(contract.unfollow_account(old), Ok(()));
+            set_caller(account(125));
+            assert_eq!(contract.unfollow_account(old), Ok(()));
+            // following the old id follows the new account
+            set_caller(account(200));
+            assert_eq!(contract.follow_account(old), Ok(()));
+            assert!(contract.set_contains(FOLLOWING_SET, account(200), new));
+            assert_eq!(contract.follow_account(old), Err(Error::CannotFollow));
+            assert_eq!(contract.set_len(FOLLOWERS_SET, new) + contract.set_len(FOLLOWERS_SET, old), 119);
+            assert_eq!(
+                contract.account_followers_map.get(new).unwrap_or_default()
+                + contract.account_followers_map.get(old).unwrap_or_default(),
+                119
+            );
+
+            set_caller(new);
+            assert_eq!(contract.continue_account_migration(100), Ok(()));
+            assert!(!contract.migration_in_progress_map.contains(new));
+            assert_eq!(contract.set_len(FOLLOWERS_SET, new), 119);
+            assert_eq!(contract.account_followers_map.get(new), Some(119));
+            assert_eq!(contract.account_followers_map.get(old), Some(0));
+            assert!(!contract.set_contains(FOLLOWING_SET, account(125), new));
         }
 
-        // END OF MESSAGE LIST
+        #[ink::test]
+        fn migrated_endorser_cannot_be_paid_again() {
+            let mut contract = ContractStorage::new();
+            let (old, new) = (account(1), account(2));
+            let message_id = send_cat_ad(&mut contract, account(50));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(2 * 86400000);
+            set_caller(old);
+            assert_eq!(contract.set_interests(b"cats".to_vec()), Ok(()));
+            // wait out the endorsement rules
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(4 * 86400000);
+            set_caller(old);
+            assert_eq!(contract.elevate_paid_message(message_id), Ok(()));
+
+            migrate(&mut contract, old, new);
+            assert_eq!(contract.get_endorsement_eligibility(new, message_id), Err(Error::DuplicateEndorsement));
+            set_caller(new);
+            assert_eq!(contract.elevate_paid_message(message_id), Err(Error::DuplicateEndorsement));
+            assert_eq!(contract.paid_message_map.get(message_id).unwrap_or_default().endorser_count, 1);
+        }
 
+        #[ink::test]
+        fn migrations_chain_to_the_latest_account() {
+            let mut contract = ContractStorage::new();
+            let (a, b, c) = (account(1), account(2), account(3));
+            let message_id = send_cat_ad(&mut contract, account(50));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(2 * 86400000);
+            set_caller(a);
+            assert_eq!(contract.set_interests(b"cats".to_vec()), Ok(()));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(4 * 86400000);
+            set_caller(a);
+            assert_eq!(contract.elevate_paid_message(message_id), Ok(()));
+            assert_eq!(contract.add_follow(account(10), a), Ok(()));
+
+            migrate(&mut contract, a, b);
+            assert!(!contract.migration_in_progress_map.contains(b));
+            // an account that has moved cannot be migrated to again
+            set_caller(account(20));
+            assert_eq!(contract.initiate_account_migration(a), Err(Error::AccountNotEmpty));
+            migrate(&mut contract, b, c);
+            assert!(!contract.migration_in_progress_map.contains(c));
+
+            assert_eq!(contract.current_account(a), c);
+            assert_eq!(contract.current_account(b), c);
+            assert!(contract.set_contains(FOLLOWING_SET, account(10), c));
+            assert_eq!(contract.account_followers_map.get(c), Some(1));
+            assert_eq!(contract.get_endorsement_eligibility(c, message_id), Err(Error::DuplicateEndorsement));
+            set_caller(account(11));
+            assert_eq!(contract.follow_account(a), Ok(()));
+            assert!(contract.set_contains(FOLLOWING_SET, account(11), c));
+            assert_eq!(contract.account_followers_map.get(c), Some(2));
+        }
     }
-    // END OF CONTRACT STORAGE
 
 }